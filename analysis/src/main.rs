@@ -111,9 +111,14 @@ fn main() -> io::Result<()> {
             current_metric.abbreviated_name = match current_metric.name.as_str() {
                 "naive_fragmented" => "NF".to_string(),
                 "contiguous_strided" => "CS".to_string(),
+                "contiguous_strided_packed" => "CSP".to_string(),
                 "contiguous_parallel_strided" => "CPS".to_string(),
                 "contiguous_tiled" => "CT".to_string(),
+                "contiguous_tiled_packed" => "CTP".to_string(),
+                "contiguous_tiled_strassen" => "CTS".to_string(),
+                "contiguous_tiled_recursive" => "CTR".to_string(),
                 "contiguous_parallel_tiled" => "CPT".to_string(),
+                "contiguous_parallel_tiled_strassen" => "CPTS".to_string(),
                 _ => current_metric.name.clone(),
             };
             collecting = true;
@@ -1,25 +1,45 @@
 extern crate rayon;
 
-use rand::Rng;
+mod io;
+mod linalg;
+#[allow(clippy::module_inception)]
 mod linear_algebra_tests;
+mod operators;
+pub mod sparse;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::ops::{Neg, Range};
+
+pub use linalg::{Lu, Qr, Svd, SymmetricEigen};
+pub use matrix_core::io::binary::{BinaryElement, MappedMatrix};
+pub use matrix_core::io::csv::CsvFormat;
+pub use matrix_core::io::market::{MarketField, MarketFormat, MarketHeader, MarketSymmetry};
+pub use matrix_core::io::npy::NpyElement;
+pub use matrix_core::io::FormatError;
+use matrix_core::{gemm, view};
+pub use matrix_core::{
+    Complex, CooMatrix, CscMatrix, CsrMatrix, MatrixError, MatrixOps, MatrixView, MatrixViewMut,
+    Real, Scalar,
+};
 
 use rayon::prelude::*;
 
+/// Rows of the result per rayon task when `multiply` goes through `gemm`.
+const SIMD_BAND_ROWS: usize = 64;
+
 #[derive(Clone, Serialize, Deserialize)]
-pub struct Matrix {
+pub struct Matrix<T = f64> {
     pub rows: usize,
     pub cols: usize,
-    pub data: Vec<f64>,
+    pub data: Vec<T>,
 }
 
-impl Matrix {
-    pub fn random(rows: usize, cols: usize) -> Matrix {
-        let mut buffer = Vec::<f64>::with_capacity(rows * cols);
+impl<T: Scalar> Matrix<T> {
+    pub fn random(rows: usize, cols: usize) -> Matrix<T> {
+        let mut buffer = Vec::<T>::with_capacity(rows * cols);
 
         for _ in 0..rows * cols {
-            let num = rand::thread_rng().gen::<f64>() * 2.0 - 1.0;
+            let num = T::random();
 
             buffer.push(num);
         }
@@ -31,127 +51,184 @@ impl Matrix {
         }
     }
 
-    pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Matrix {
-        assert!(data.len() - 1 != rows * cols, "Invalid Size");
-        Matrix { rows, cols, data }
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
+        Matrix::try_new(rows, cols, data).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn zeros(rows: usize, cols: usize) -> Matrix {
+    pub fn try_new(rows: usize, cols: usize, data: Vec<T>) -> Result<Matrix<T>, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::InvalidDataLength {
+                expected: rows * cols,
+                actual: data.len(),
+            });
+        }
+
+        Ok(Matrix { rows, cols, data })
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
         Matrix {
             rows,
             cols,
-            data: vec![0.0; cols * rows],
+            data: vec![T::zero(); cols * rows],
         }
     }
 
-    pub fn add(&self, other: &Matrix) -> Matrix {
+    pub fn add(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.rows != other.rows || self.cols != other.cols {
-            panic!(
-                "Cannot add matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![0.0; self.rows * self.cols];
+        let mut result_data = vec![T::zero(); self.rows * self.cols];
 
         result_data
             .par_chunks_mut(self.cols)
             .enumerate()
             .for_each(|(i, result_row)| {
-                for j in 0..self.cols {
-                    result_row[j] = self.data[i * self.cols + j] + other.data[i * self.cols + j];
-                }
+                let row = i * self.cols..(i + 1) * self.cols;
+                T::add_slices(&self.data[row.clone()], &other.data[row], result_row);
             });
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
     }
 
-    pub fn subtract(&self, other: &Matrix) -> Matrix {
-        assert!(
-            self.rows == other.rows && self.cols == other.cols,
-            "Cannot subtract matrices with different dimensions"
-        );
+    pub fn subtract(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_subtract(other).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        let mut result_data = vec![0.0; self.rows * self.cols];
+    pub fn try_subtract(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "subtract",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        let mut result_data = vec![T::zero(); self.rows * self.cols];
 
         result_data
             .par_chunks_mut(self.cols)
             .enumerate()
             .for_each(|(i, result_row)| {
-                for j in 0..self.cols {
-                    result_row[j] = self.data[i * self.cols + j] - other.data[i * self.cols + j];
-                }
+                let row = i * self.cols..(i + 1) * self.cols;
+                T::sub_slices(&self.data[row.clone()], &other.data[row], result_row);
             });
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
+    }
+
+    pub fn multiply(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_multiply(other).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn multiply(&self, other: &Matrix) -> Matrix {
+    pub fn try_multiply(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.cols != other.rows {
-            panic!(
-                "Cannot multiply matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![0.0; self.rows * other.cols];
-
-        result_data
-            .par_chunks_mut(other.cols)
-            .enumerate()
-            .for_each(|(i, result_row)| {
-                for j in 0..other.cols {
-                    let mut sum = 0.0;
-                    for k in 0..self.cols {
-                        sum += self.data[i * self.cols + k] * other.data[k * other.cols + j];
-                    }
-                    result_row[j] = sum;
-                }
-            });
+        let mut result_data = vec![T::zero(); self.rows * other.cols];
+        self.multiply_accumulate(other, &mut result_data);
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: other.cols,
             data: result_data,
+        })
+    }
+
+    /// `result += self * other`, with `result` holding `self.rows x
+    /// other.cols` elements. Shared by `multiply` and `multiply_into`.
+    fn multiply_accumulate(&self, other: &Matrix<T>, result_data: &mut [T]) {
+        if T::has_simd_kernel() {
+            // com um micro-kernel SIMD cada tarefa faz o gemm de uma faixa
+            // de linhas do resultado
+            result_data
+                .par_chunks_mut((SIMD_BAND_ROWS * other.cols).max(1))
+                .enumerate()
+                .for_each(|(band, result_band)| {
+                    let first_row = band * SIMD_BAND_ROWS;
+                    gemm::gemm(
+                        result_band.len() / other.cols,
+                        other.cols,
+                        self.cols,
+                        &self.data[first_row * self.cols..],
+                        self.cols,
+                        &other.data,
+                        other.cols,
+                        result_band,
+                        other.cols,
+                    );
+                });
+        } else {
+            result_data
+                .par_chunks_mut(other.cols.max(1))
+                .enumerate()
+                .for_each(|(i, result_row)| {
+                    for (j, result) in result_row.iter_mut().enumerate() {
+                        let mut sum = T::zero();
+                        for k in 0..self.cols {
+                            sum += self.data[i * self.cols + k] * other.data[k * other.cols + j];
+                        }
+                        *result += sum;
+                    }
+                });
         }
     }
 
-    pub fn hadamard_product(&self, other: &Matrix) -> Matrix {
+    pub fn hadamard_product(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_hadamard_product(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_hadamard_product(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.rows != other.rows || self.cols != other.cols {
-            panic!(
-                "Cannot apply hadamard product to matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "hadamard product",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![0.0; self.rows * self.cols];
+        let mut result_data = vec![T::zero(); self.rows * self.cols];
 
         result_data
             .par_chunks_mut(self.cols)
             .enumerate()
             .for_each(|(i, result_row)| {
-                for j in 0..self.cols {
-                    result_row[j] = self.data[i * self.cols + j] * other.data[i * self.cols + j];
-                }
+                let row = i * self.cols..(i + 1) * self.cols;
+                T::mul_slices(&self.data[row.clone()], &other.data[row], result_row);
             });
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
     }
 
-    pub fn transpose(&self) -> Matrix {
-        let mut buffer = vec![0.0; self.cols * self.rows];
+    pub fn transpose(&self) -> Matrix<T> {
+        let mut buffer = vec![T::zero(); self.cols * self.rows];
 
         for i in 0..self.rows {
             for j in 0..self.cols {
@@ -166,18 +243,14 @@ impl Matrix {
         }
     }
 
-    pub fn identity(size: usize) -> Matrix{
-        let mut result_data = vec![0.0; size * size];
+    pub fn identity(size: usize) -> Matrix<T> {
+        let mut result_data = vec![T::zero(); size * size];
 
         result_data
             .par_chunks_mut(size)
             .enumerate()
             .for_each(|(i, result_row)| {
-                for j in 0..size {
-                    if j.eq(&i) {
-                        result_row[j] = 1.0;
-                    }
-                }
+                result_row[i] = T::one();
             });
 
         Matrix {
@@ -187,17 +260,401 @@ impl Matrix {
         }
     }
 
-    pub fn apply_function(&self, func: &dyn Fn(f64) -> f64) -> Matrix {
-        let a: Vec<f64> = self.data.iter().map(|&val| func(val)).collect();
-        return Matrix {
+    pub fn apply_function(&self, func: &dyn Fn(T) -> T) -> Matrix<T> {
+        let a: Vec<T> = self.data.iter().map(|&val| func(val)).collect();
+        Matrix {
             cols: self.cols,
             rows: self.rows,
             data: a,
-        };
+        }
+    }
+
+    /// `self += other` in place, without allocating.
+    pub fn add_assign(&mut self, other: &Matrix<T>) {
+        self.try_add_assign(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add_assign(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        let cols = self.cols.max(1);
+        self.data
+            .par_chunks_mut(cols)
+            .zip(other.data.par_chunks(cols))
+            .for_each(|(row, other_row)| T::add_assign_slices(row, other_row));
+
+        Ok(())
+    }
+
+    /// `self -= other` in place.
+    pub fn sub_assign(&mut self, other: &Matrix<T>) {
+        self.try_sub_assign(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_sub_assign(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "subtract",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        let cols = self.cols.max(1);
+        self.data
+            .par_chunks_mut(cols)
+            .zip(other.data.par_chunks(cols))
+            .for_each(|(row, other_row)| T::sub_assign_slices(row, other_row));
+
+        Ok(())
+    }
+
+    /// Element-wise `self *= other` in place.
+    pub fn hadamard_assign(&mut self, other: &Matrix<T>) {
+        self.try_hadamard_assign(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_hadamard_assign(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "hadamard product",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        let cols = self.cols.max(1);
+        self.data
+            .par_chunks_mut(cols)
+            .zip(other.data.par_chunks(cols))
+            .for_each(|(row, other_row)| T::mul_assign_slices(row, other_row));
+
+        Ok(())
+    }
+
+    /// `apply_function` writing over the elements of `self`, in parallel, so
+    /// `func` has to be `Sync`.
+    pub fn apply_function_mut(&mut self, func: &(dyn Fn(T) -> T + Sync)) {
+        self.data.par_iter_mut().for_each(|x| *x = func(*x));
+    }
+
+    /// GEMM-style `c = alpha * a * b + beta * c`, written into the existing
+    /// buffer of `c`.
+    pub fn multiply_into(a: &Matrix<T>, b: &Matrix<T>, alpha: T, beta: T, c: &mut Matrix<T>) {
+        Matrix::try_multiply_into(a, b, alpha, beta, c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multiply_into(
+        a: &Matrix<T>,
+        b: &Matrix<T>,
+        alpha: T,
+        beta: T,
+        c: &mut Matrix<T>,
+    ) -> Result<(), MatrixError> {
+        if a.cols != b.rows {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (a.rows, a.cols),
+                rhs: (b.rows, b.cols),
+            });
+        }
+        if c.rows != a.rows || c.cols != b.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply into",
+                lhs: (a.rows, b.cols),
+                rhs: (c.rows, c.cols),
+            });
+        }
+
+        // com beta = 0 o C antigo é ignorado, mesmo se tiver NaN
+        if beta == T::zero() {
+            c.data.par_iter_mut().for_each(|x| *x = T::zero());
+        } else if beta != T::one() {
+            c.data.par_iter_mut().for_each(|x| *x = beta * *x);
+        }
+
+        if alpha == T::zero() {
+            return Ok(());
+        }
+
+        // o produto sai do mesmo kernel de `multiply`: com alpha = 1 ele
+        // acumula direto em C, senão vai para um buffer e alpha é aplicado
+        // uma vez por elemento
+        if alpha == T::one() {
+            a.multiply_accumulate(b, &mut c.data);
+        } else {
+            let mut product = vec![T::zero(); c.data.len()];
+            a.multiply_accumulate(b, &mut product);
+            c.data
+                .par_iter_mut()
+                .zip(product.par_iter())
+                .for_each(|(x, &y)| *x += alpha * y);
+        }
+
+        Ok(())
+    }
+
+    /// Borrowed view of the whole matrix, see `matrix_core::view`.
+    pub fn view(&self) -> MatrixView<'_, T> {
+        MatrixView::from_row_major(&self.data, self.rows, self.cols)
+    }
+
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut::from_row_major(&mut self.data, self.rows, self.cols)
+    }
+
+    pub fn row(&self, i: usize) -> MatrixView<'_, T> {
+        self.view().row(i)
+    }
+
+    pub fn col(&self, j: usize) -> MatrixView<'_, T> {
+        self.view().col(j)
+    }
+
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, T> {
+        self.view().submatrix(rows, cols)
+    }
+
+    pub fn submatrix_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, T> {
+        self.view_mut().into_submatrix(rows, cols)
+    }
+
+    /// `Aᵀ` as a view over the same data, without copying.
+    pub fn transposed(&self) -> MatrixView<'_, T> {
+        self.view().transpose()
+    }
+
+    /// Copies a view into a new matrix.
+    pub fn from_view(view: &MatrixView<T>) -> Matrix<T> {
+        Matrix {
+            rows: view.rows(),
+            cols: view.cols(),
+            data: view.to_vec(),
+        }
+    }
+
+    /// `add` for views, e.g. two blocks of the same matrix.
+    pub fn add_views(a: &MatrixView<T>, b: &MatrixView<T>) -> Matrix<T> {
+        Matrix::try_add_views(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add_views(a: &MatrixView<T>, b: &MatrixView<T>) -> Result<Matrix<T>, MatrixError> {
+        let mut result = Matrix::zeros(a.rows(), a.cols());
+        if a.rows() != b.rows() || a.cols() != b.cols() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (a.rows(), a.cols()),
+                rhs: (b.rows(), b.cols()),
+            });
+        }
+
+        // uma linha do resultado por tarefa
+        let cols = a.cols();
+        result
+            .data
+            .par_chunks_mut(cols.max(1))
+            .enumerate()
+            .try_for_each(|(i, result_row)| {
+                let mut out = MatrixViewMut::from_row_major(result_row, 1, cols);
+                view::add(&a.row(i), &b.row(i), &mut out)
+            })?;
+        Ok(result)
+    }
+
+    /// `multiply` for views: blocks, rows, columns or transposed views are
+    /// read in place.
+    pub fn multiply_views(a: &MatrixView<T>, b: &MatrixView<T>) -> Matrix<T> {
+        Matrix::try_multiply_views(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multiply_views(
+        a: &MatrixView<T>,
+        b: &MatrixView<T>,
+    ) -> Result<Matrix<T>, MatrixError> {
+        if a.cols() != b.rows() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (a.rows(), a.cols()),
+                rhs: (b.rows(), b.cols()),
+            });
+        }
+
+        let mut result = Matrix::zeros(a.rows(), b.cols());
+        // faixas de linhas do resultado em paralelo, cada uma com as linhas
+        // correspondentes de `a`
+        let (rows, cols) = (a.rows(), b.cols());
+        let band = rows.div_ceil(rayon::current_num_threads()).max(1);
+        result
+            .data
+            .par_chunks_mut((band * cols).max(1))
+            .enumerate()
+            .try_for_each(|(index, result_band)| {
+                let i0 = index * band;
+                let i1 = (i0 + band).min(rows);
+                let mut out = MatrixViewMut::from_row_major(result_band, i1 - i0, cols);
+                view::multiply_add(&a.submatrix(i0..i1, 0..a.cols()), b, &mut out)
+            })?;
+        Ok(result)
+    }
+
+    /// Non-zero entries as CSR, see `matrix_core::sparse`.
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix::from_dense(&self.data, self.rows, self.cols)
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix::from_dense(&self.data, self.rows, self.cols)
+    }
+
+    pub fn from_csr(sparse: &CsrMatrix<T>) -> Matrix<T> {
+        Matrix {
+            rows: sparse.rows(),
+            cols: sparse.cols(),
+            data: sparse.to_dense(),
+        }
+    }
+
+    pub fn from_csc(sparse: &CscMatrix<T>) -> Matrix<T> {
+        Matrix {
+            rows: sparse.rows(),
+            cols: sparse.cols(),
+            data: sparse.to_dense(),
+        }
+    }
+
+    /// `A * B` for a sparse `A`; only the non-zeros of A are visited.
+    pub fn sparse_multiply(a: &CsrMatrix<T>, b: &Matrix<T>) -> Matrix<T> {
+        Matrix::try_sparse_multiply(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_sparse_multiply(a: &CsrMatrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let b = b.view();
+        a.check_dense(&b)?;
+
+        // uma linha do resultado por tarefa, como no `add`
+        let mut result = Matrix::zeros(a.rows(), b.cols());
+        result
+            .data
+            .par_chunks_mut(b.cols().max(1))
+            .enumerate()
+            .for_each(|(i, result_row)| a.row_times_dense(i, &b, result_row));
+        Ok(result)
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Matrix<Complex<T>> {
+    pub fn conjugate(&self) -> Matrix<Complex<T>> {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.par_iter().map(|z| z.conj()).collect(),
+        }
+    }
+
+    /// Hermitian adjoint `Aᴴ`: the transpose with every element conjugated.
+    pub fn conjugate_transpose(&self) -> Matrix<Complex<T>> {
+        let mut buffer = vec![Complex::zero(); self.cols * self.rows];
+
+        // Linha j do resultado é a coluna j de self, conjugada
+        buffer
+            .par_chunks_mut(self.rows)
+            .enumerate()
+            .for_each(|(j, result_row)| {
+                for (i, result) in result_row.iter_mut().enumerate() {
+                    *result = self.data[i * self.cols + j].conj();
+                }
+            });
+
+        Matrix {
+            rows: self.cols,
+            cols: self.rows,
+            data: buffer,
+        }
+    }
+}
+
+impl<T: Scalar> MatrixOps for Matrix<T> {
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.data[i * self.cols + j]
+    }
+
+    fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::zeros(rows, cols)
+    }
+
+    fn random(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::random(rows, cols)
+    }
+
+    fn identity(size: usize) -> Matrix<T> {
+        Matrix::identity(size)
+    }
+
+    fn add(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.add(other)
+    }
+
+    fn try_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_add(other)
+    }
+
+    fn subtract(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.subtract(other)
+    }
+
+    fn try_subtract(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_subtract(other)
+    }
+
+    fn multiply(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.multiply(other)
+    }
+
+    fn try_multiply(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_multiply(other)
+    }
+
+    fn hadamard_product(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.hadamard_product(other)
+    }
+
+    fn try_hadamard_product(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_hadamard_product(other)
+    }
+
+    fn transpose(&self) -> Matrix<T> {
+        self.transpose()
+    }
+
+    fn apply_function(&self, func: &dyn Fn(T) -> T) -> Matrix<T> {
+        self.apply_function(func)
     }
 }
 
-impl fmt::Display for Matrix {
+impl<T: Scalar> fmt::Display for Matrix<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let max_width = self
             .data
@@ -227,9 +684,9 @@ impl fmt::Display for Matrix {
     }
 }
 
-impl From<Vec<f64>> for Matrix {
+impl<T: Scalar> From<Vec<T>> for Matrix<T> {
     /// This method will always return a matrix with rows = vec.len() and cols = 1
-    fn from(vec: Vec<f64>) -> Self {
+    fn from(vec: Vec<T>) -> Self {
         let rows = vec.len();
         let cols = 1;
         Matrix {
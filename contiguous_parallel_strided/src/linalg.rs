@@ -0,0 +1,244 @@
+use matrix_core::linalg::lu::LuFactors;
+use matrix_core::linalg::qr::QrFactors;
+use matrix_core::linalg::{cholesky, eigen, svd};
+use matrix_core::{MatrixError, Real};
+
+use crate::Matrix;
+
+/// `P * A = L * U`, returned by [`Matrix::lu`].
+pub struct Lu<T = f64> {
+    factors: LuFactors<T>,
+}
+
+impl<T: Real> Lu<T> {
+    /// Unit lower triangular factor.
+    pub fn l(&self) -> Matrix<T> {
+        let n = self.factors.n;
+        Matrix::new(n, n, self.factors.l())
+    }
+
+    pub fn u(&self) -> Matrix<T> {
+        let n = self.factors.n;
+        Matrix::new(n, n, self.factors.u())
+    }
+
+    /// Row `i` of `P * A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.factors.permutation
+    }
+
+    pub fn determinant(&self) -> T {
+        self.factors.determinant()
+    }
+
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let n = self.factors.n;
+        if b.rows != n {
+            return Err(MatrixError::DimensionMismatch {
+                op: "solve",
+                lhs: (n, n),
+                rhs: (b.rows, b.cols),
+            });
+        }
+
+        let x = self.factors.solve(&b.data, b.cols)?;
+        Ok(Matrix::new(n, b.cols, x))
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        let n = self.factors.n;
+        Ok(Matrix::new(n, n, self.factors.inverse()?))
+    }
+}
+
+/// `A = Q * R` by Householder reflections, returned by [`Matrix::qr`].
+pub struct Qr<T = f64> {
+    factors: QrFactors<T>,
+}
+
+impl<T: Real> Qr<T> {
+    /// Thin Q, `rows x min(rows, cols)` with orthonormal columns.
+    pub fn q(&self) -> Matrix<T> {
+        let f = &self.factors;
+        Matrix::new(f.rows, f.tau.len(), f.q())
+    }
+
+    /// Upper triangular R, `min(rows, cols) x cols`.
+    pub fn r(&self) -> Matrix<T> {
+        let f = &self.factors;
+        Matrix::new(f.tau.len(), f.cols, f.r())
+    }
+
+    pub fn least_squares(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let f = &self.factors;
+        if b.rows != f.rows {
+            return Err(MatrixError::DimensionMismatch {
+                op: "solve",
+                lhs: (f.rows, f.cols),
+                rhs: (b.rows, b.cols),
+            });
+        }
+
+        let x = f.least_squares(&b.data, b.cols)?;
+        Ok(Matrix::new(f.cols, b.cols, x))
+    }
+}
+
+/// Eigenvalues in ascending order and, in column `i` of `vectors`, the unit
+/// eigenvector of `values[i]`. Returned by [`Matrix::symmetric_eigen`].
+pub struct SymmetricEigen<T = f64> {
+    pub values: Vec<T>,
+    pub vectors: Matrix<T>,
+}
+
+/// `A = U * Σ * Vᵀ`, returned by [`Matrix::svd`] and [`Matrix::svd_full`].
+/// The singular values are in descending order.
+pub struct Svd<T = f64> {
+    pub u: Matrix<T>,
+    pub singular_values: Vec<T>,
+    pub vt: Matrix<T>,
+}
+
+impl<T: Real> Svd<T> {
+    /// `Σ` as a `u.cols x vt.rows` matrix, ready to multiply.
+    pub fn sigma(&self) -> Matrix<T> {
+        let mut sigma = Matrix::zeros(self.u.cols, self.vt.rows);
+        for (i, &value) in self.singular_values.iter().enumerate() {
+            sigma.data[i * sigma.cols + i] = value;
+        }
+        sigma
+    }
+}
+
+impl<T: Real> Matrix<T> {
+    pub fn lu(&self) -> Result<Lu<T>, MatrixError> {
+        self.check_square("factor")?;
+
+        Ok(Lu {
+            factors: LuFactors::factor(self.data.clone(), self.rows),
+        })
+    }
+
+    pub fn determinant(&self) -> Result<T, MatrixError> {
+        self.check_square("compute the determinant of")?;
+        Ok(self.lu()?.determinant())
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        self.check_square("invert")?;
+        self.lu()?.inverse()
+    }
+
+    /// Solves `self * x = b`; `b` may have several columns.
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.check_square("solve with")?;
+        self.lu()?.solve(b)
+    }
+
+    /// Lower triangular `L` with `self = L * Lᵀ`. Only the lower triangle of
+    /// `self` is read.
+    pub fn cholesky(&self) -> Result<Matrix<T>, MatrixError> {
+        self.check_square("factor")?;
+
+        let n = self.rows;
+        Ok(Matrix::new(n, n, cholesky::factor(self.data.clone(), n)?))
+    }
+
+    /// Solves `self * x = b` for a symmetric positive definite `self`.
+    pub fn solve_spd(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let l = self.cholesky()?;
+        check_rhs(self, b)?;
+
+        let x = cholesky::solve(&l.data, self.rows, &b.data, b.cols);
+        Ok(Matrix::new(self.rows, b.cols, x))
+    }
+
+    pub fn qr(&self) -> Qr<T> {
+        Qr {
+            factors: QrFactors::factor(self.data.clone(), self.rows, self.cols),
+        }
+    }
+
+    /// `x` minimizing `||self * x - b||`, for `rows >= cols` and full column
+    /// rank. Each column of `b` is solved independently.
+    pub fn least_squares(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.qr().least_squares(b)
+    }
+
+    /// Eigen decomposition `self = V * diag(values) * Vᵀ` of a symmetric
+    /// matrix: cyclic Jacobi up to `eigen::JACOBI_MAX_SIZE`, Householder
+    /// tridiagonalization + implicit QR above it.
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen<T>, MatrixError> {
+        self.check_square("compute eigenvalues of")?;
+
+        let n = self.rows;
+        let eigen = eigen::symmetric_eigen(self.data.clone(), n)?;
+
+        Ok(SymmetricEigen {
+            values: eigen.values,
+            vectors: Matrix::new(n, n, eigen.vectors),
+        })
+    }
+
+    /// Thin SVD: `U` is `rows x p` and `Vᵀ` is `p x cols`, `p = min(rows, cols)`.
+    pub fn svd(&self) -> Result<Svd<T>, MatrixError> {
+        self.svd_with(false)
+    }
+
+    /// Full SVD: `U` is `rows x rows` and `Vᵀ` is `cols x cols`.
+    pub fn svd_full(&self) -> Result<Svd<T>, MatrixError> {
+        self.svd_with(true)
+    }
+
+    /// Number of singular values above `tolerance`.
+    pub fn rank(&self, tolerance: T) -> Result<usize, MatrixError> {
+        Ok(svd::svd(&self.data, self.rows, self.cols, false)?.rank(tolerance))
+    }
+
+    /// Moore-Penrose pseudo-inverse, ignoring singular values below
+    /// `max(rows, cols) * eps * sigma_max`.
+    pub fn pseudo_inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        let pseudo_inverse = svd::svd(&self.data, self.rows, self.cols, false)?.pseudo_inverse();
+        Ok(Matrix::new(self.cols, self.rows, pseudo_inverse))
+    }
+
+    /// `sigma_max / sigma_min`, infinite only when `sigma_min` is exactly zero
+    /// (a singular matrix usually gives a huge finite value).
+    pub fn condition_number(&self) -> Result<T, MatrixError> {
+        Ok(svd::svd(&self.data, self.rows, self.cols, false)?.condition_number())
+    }
+
+    fn svd_with(&self, full: bool) -> Result<Svd<T>, MatrixError> {
+        let svd = svd::svd(&self.data, self.rows, self.cols, full)?;
+
+        Ok(Svd {
+            u: Matrix::new(self.rows, svd.u_cols, svd.u),
+            singular_values: svd.singular_values,
+            vt: Matrix::new(svd.vt_rows, self.cols, svd.vt),
+        })
+    }
+
+    fn check_square(&self, op: &'static str) -> Result<(), MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::NotSquare {
+                op,
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn check_rhs<T: Real>(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), MatrixError> {
+    if b.rows != a.rows {
+        return Err(MatrixError::DimensionMismatch {
+            op: "solve",
+            lhs: (a.rows, a.cols),
+            rhs: (b.rows, b.cols),
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+// Operadores do `std::ops` para `Matrix`, gerados pela macro
+// `matrix_core::impl_contiguous_operators`. Eles chamam os métodos do
+// `MatrixOps`, então o `+` e o `*` usam os kernels paralelos (rayon) deste
+// crate.
+
+use crate::Matrix;
+
+matrix_core::impl_contiguous_operators!(Matrix);
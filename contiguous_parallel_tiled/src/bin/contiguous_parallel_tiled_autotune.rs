@@ -0,0 +1,62 @@
+use std::env;
+
+use matrix_core::{cache, tuning, Scalar};
+
+use contiguous_parallel_tiled::{autotune, Tuning};
+
+// Mede o `multiply` com cada tamanho de bloco e ordem de loop e salva o mais
+// rápido em `matrix_tuning.conf` (ou em `MATRIX_TUNING_FILE`), na seção do
+// crate e do DTYPE medido. Os outros binários e o `MatrixOps` passam a usar
+// esse valor para o mesmo tipo.
+
+fn main() {
+    let size = env::var("SIZE").unwrap_or_else(|_| "512".to_string());
+    let repetitions = env::var("REPETITIONS").unwrap_or_else(|_| "3".to_string());
+    let size = size.parse::<usize>().unwrap();
+    let repetitions = repetitions.parse::<usize>().unwrap();
+    let dtype = env::var("DTYPE").unwrap_or_else(|_| "f64".to_string());
+
+    match dtype.as_str() {
+        "f32" => run::<f32>(size, repetitions),
+        "f64" => run::<f64>(size, repetitions),
+        other => panic!("DTYPE desconhecido: {} (use f32 ou f64)", other),
+    }
+}
+
+fn run<T: Scalar>(size: usize, repetitions: usize) {
+    let topology = cache::topology();
+    println!(
+        "cache: L1 {} KiB, L2 {} KiB, L3 {} KiB, bloco calculado {}\n",
+        topology.l1 / 1024,
+        topology.l2 / 1024,
+        topology.l3 / 1024,
+        topology.tile_sizes::<T>().multiply
+    );
+
+    let measurements = autotune::<T>(size, repetitions);
+
+    println!("| block_size | loop_order | tempo (ms) |");
+    println!("| ---: | :---: | ---: |");
+    for measurement in &measurements {
+        println!(
+            "| {} | {} | {:.1} |",
+            measurement.block_size,
+            measurement.loop_order,
+            measurement.seconds * 1000.0
+        );
+    }
+
+    let best = measurements[0];
+    let tuning = Tuning {
+        block_size: best.block_size,
+        loop_order: best.loop_order,
+    };
+    let path = tuning::save(&tuning::section::<T>(env!("CARGO_PKG_NAME")), tuning).unwrap();
+
+    println!(
+        "\nblock_size = {}, loop_order = {} salvo em {}",
+        tuning.block_size,
+        tuning.loop_order,
+        path.display()
+    );
+}
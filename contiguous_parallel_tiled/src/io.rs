@@ -0,0 +1,155 @@
+// Leitura e escrita de `Matrix` em arquivos, usando os formatos de
+// `matrix_core::io` (Matrix Market, NumPy, CSV e o binário próprio) sobre o buffer
+// row-major.
+
+use std::fmt::LowerExp;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use matrix_core::io::binary::{self, BinaryElement, Layout};
+use matrix_core::io::csv::{self, CsvFormat};
+use matrix_core::io::market::{self, MarketHeader};
+use matrix_core::io::npy::{self, NpyElement, NpzArray};
+use matrix_core::io::FormatError;
+
+use crate::{Matrix, Scalar};
+
+impl<T: Scalar + FromStr> Matrix<T> {
+    /// Reads a Matrix Market file, coordinate or array. Sparse files are
+    /// stored dense, so mind the size of SuiteSparse inputs.
+    pub fn from_matrix_market<R: BufRead>(reader: R) -> Result<Matrix<T>, FormatError> {
+        let (rows, cols, data) = market::read_dense(reader)?;
+        Ok(Matrix { rows, cols, data })
+    }
+
+    pub fn read_matrix_market<P: AsRef<Path>>(path: P) -> Result<Matrix<T>, FormatError> {
+        Matrix::from_matrix_market(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a delimited text file, one line per row. With
+    /// `format.has_header` the first line is skipped.
+    pub fn from_csv_reader<R: BufRead>(
+        reader: R,
+        format: CsvFormat,
+    ) -> Result<Matrix<T>, FormatError> {
+        let table = csv::read(reader, format)?;
+        Ok(Matrix {
+            rows: table.rows,
+            cols: table.cols,
+            data: table.data,
+        })
+    }
+
+    pub fn read_csv<P: AsRef<Path>>(path: P, format: CsvFormat) -> Result<Matrix<T>, FormatError> {
+        Matrix::from_csv_reader(BufReader::new(File::open(path)?), format)
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    pub fn to_csv_writer<W: Write>(&self, writer: W, format: CsvFormat) -> Result<(), FormatError> {
+        csv::write(writer, format, self.rows, self.cols, &self.data)
+    }
+
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P, format: CsvFormat) -> Result<(), FormatError> {
+        self.to_csv_writer(BufWriter::new(File::create(path)?), format)
+    }
+}
+
+impl<T: Scalar + LowerExp> Matrix<T> {
+    pub fn to_matrix_market<W: Write>(
+        &self,
+        writer: W,
+        header: MarketHeader,
+    ) -> Result<(), FormatError> {
+        market::write_dense(writer, header, self.rows, self.cols, &self.data)
+    }
+
+    pub fn write_matrix_market<P: AsRef<Path>>(
+        &self,
+        path: P,
+        header: MarketHeader,
+    ) -> Result<(), FormatError> {
+        self.to_matrix_market(BufWriter::new(File::create(path)?), header)
+    }
+}
+
+impl<T: NpyElement> Matrix<T> {
+    /// Reads a NumPy `.npy`, C or Fortran order, `<f8` or `<f4`. A 1-D
+    /// array becomes a column.
+    pub fn from_npy<R: Read>(reader: R) -> Result<Matrix<T>, FormatError> {
+        let (rows, cols, data) = npy::read(reader)?;
+        Ok(Matrix { rows, cols, data })
+    }
+
+    pub fn read_npy<P: AsRef<Path>>(path: P) -> Result<Matrix<T>, FormatError> {
+        Matrix::from_npy(BufReader::new(File::open(path)?))
+    }
+
+    /// Writes a C-order `.npy` that `numpy.load` reads as a 2-D array.
+    pub fn to_npy<W: Write>(&self, writer: W) -> Result<(), FormatError> {
+        npy::write(writer, self.rows, self.cols, &self.data)
+    }
+
+    pub fn write_npy<P: AsRef<Path>>(&self, path: P) -> Result<(), FormatError> {
+        self.to_npy(BufWriter::new(File::create(path)?))
+    }
+
+    /// Every array of an uncompressed `.npz` (`numpy.savez`), with its name.
+    pub fn from_npz<R: Read>(reader: R) -> Result<Vec<(String, Matrix<T>)>, FormatError> {
+        Ok(npy::read_npz(reader)?
+            .into_iter()
+            .map(
+                |NpzArray {
+                     name,
+                     rows,
+                     cols,
+                     data,
+                 }| (name, Matrix { rows, cols, data }),
+            )
+            .collect())
+    }
+
+    pub fn read_npz<P: AsRef<Path>>(path: P) -> Result<Vec<(String, Matrix<T>)>, FormatError> {
+        Matrix::from_npz(BufReader::new(File::open(path)?))
+    }
+
+    pub fn to_npz<W: Write>(writer: W, arrays: &[(&str, &Matrix<T>)]) -> Result<(), FormatError> {
+        let arrays: Vec<_> = arrays
+            .iter()
+            .map(|(name, matrix)| (*name, matrix.rows, matrix.cols, &matrix.data[..]))
+            .collect();
+        npy::write_npz(writer, &arrays)
+    }
+
+    pub fn write_npz<P: AsRef<Path>>(
+        path: P,
+        arrays: &[(&str, &Matrix<T>)],
+    ) -> Result<(), FormatError> {
+        Matrix::to_npz(BufWriter::new(File::create(path)?), arrays)
+    }
+}
+
+impl<T: BinaryElement> Matrix<T> {
+    /// Reads the binary format of `matrix_core::io::binary`, checking the
+    /// checksum. Column-major files come back row-major.
+    pub fn from_binary<R: Read>(reader: R) -> Result<Matrix<T>, FormatError> {
+        let (rows, cols, data) = binary::read(reader)?;
+        Ok(Matrix { rows, cols, data })
+    }
+
+    /// For large inputs `MappedMatrix::open` avoids the read: its `view`
+    /// goes straight into `multiply_views` or `from_view`.
+    pub fn read_binary<P: AsRef<Path>>(path: P) -> Result<Matrix<T>, FormatError> {
+        Matrix::from_binary(BufReader::new(File::open(path)?))
+    }
+
+    pub fn to_binary<W: Write>(&self, writer: W) -> Result<(), FormatError> {
+        binary::write(writer, Layout::RowMajor, self.rows, self.cols, &self.data)
+    }
+
+    pub fn write_binary<P: AsRef<Path>>(&self, path: P) -> Result<(), FormatError> {
+        self.to_binary(BufWriter::new(File::create(path)?))
+    }
+}
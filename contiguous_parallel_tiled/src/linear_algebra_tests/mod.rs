@@ -1,6 +1,12 @@
 #[cfg(test)]
 mod linear_algebra_tests {
-    use crate::Matrix;
+    use crate::sparse;
+    use crate::strassen;
+    use crate::{
+        autotune, default_tile_sizes, set_default_block_size, tuning, Complex, CsvFormat,
+        FormatError, LoopOrder, MappedMatrix, MarketField, MarketHeader, Matrix, MatrixError,
+        MatrixOps, Svd,
+    };
 
     #[test]
     fn matrix_creation() {
@@ -15,6 +21,52 @@ mod linear_algebra_tests {
         assert_eq!(matrix.data[3], 2.5);
     }
 
+    #[test]
+    fn try_new_should_reject_invalid_data_length() {
+        assert_eq!(
+            Matrix::try_new(2, 2, vec![1.0, 1.5, 2.0]).err(),
+            Some(MatrixError::InvalidDataLength {
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            Matrix::<f64>::try_new(2, 2, vec![]).err(),
+            Some(MatrixError::InvalidDataLength {
+                expected: 4,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Size")]
+    fn matrix_creation_should_fail_when_data_length_does_not_match() {
+        Matrix::new(3, 3, vec![1.0, 4.0]);
+    }
+
+    #[test]
+    fn try_operations_should_return_dimension_mismatch() {
+        let matrix_a: Matrix = Matrix::new(2, 1, vec![1.0, 4.0]);
+        let matrix_b: Matrix = Matrix::new(2, 2, vec![3.0, 5.0, 5.0, 7.0]);
+
+        assert_eq!(
+            matrix_a.try_add(&matrix_b, 16).err(),
+            Some(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (2, 1),
+                rhs: (2, 2)
+            })
+        );
+        assert!(matrix_a.try_subtract(&matrix_b, 16).is_err());
+        assert!(matrix_a.try_hadamard_product(&matrix_b).is_err());
+        assert!(matrix_a.try_multiply(&matrix_b, 16).is_err());
+
+        let multiplied_matrix: Matrix = matrix_b.try_multiply(&matrix_a, 16).unwrap();
+        assert_eq!(multiplied_matrix.rows, 2);
+        assert_eq!(multiplied_matrix.cols, 1);
+    }
+
     #[test]
     fn matrix_of_zeros() {
         let matrix: Matrix = Matrix::zeros(2, 2);
@@ -30,7 +82,7 @@ mod linear_algebra_tests {
 
     #[test]
     fn random_matrix() {
-        let matrix = Matrix::random(10, 10);
+        let matrix: Matrix = Matrix::random(10, 10);
 
         for i in 0..matrix.rows {
             assert_ne!(matrix.data[i], 0.0);
@@ -83,6 +135,52 @@ mod linear_algebra_tests {
         assert_eq!(multiplied_matrix.data[1], 83.0);
     }
 
+    #[test]
+    fn matrix_multiplication_with_partial_blocks() {
+        let matrix_a: Matrix = Matrix::random(37, 23);
+        let matrix_b: Matrix = Matrix::random(23, 41);
+        let multiplied_matrix: Matrix = matrix_a.multiply(&matrix_b, 8);
+
+        assert_eq!(multiplied_matrix.rows, 37);
+        assert_eq!(multiplied_matrix.cols, 41);
+
+        for i in 0..37 {
+            for j in 0..41 {
+                let mut expected = 0.0;
+                for k in 0..23 {
+                    expected += matrix_a.data[i * 23 + k] * matrix_b.data[k * 41 + j];
+                }
+                assert!((multiplied_matrix.data[i * 41 + j] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn f32_matrix_2x2_multiplication() {
+        let matrix_a: Matrix<f32> = Matrix::new(2, 2, vec![1.0, 10.0, 4.0, 9.0]);
+        let matrix_b: Matrix<f32> = Matrix::new(2, 2, vec![3.0, 5.0, 5.0, 7.0]);
+        let multiplied_matrix: Matrix<f32> = matrix_a.multiply(&matrix_b, 16);
+
+        assert_eq!(multiplied_matrix.data[0], 53.0f32);
+        assert_eq!(multiplied_matrix.data[1], 75.0f32);
+        assert_eq!(multiplied_matrix.data[2], 57.0f32);
+        assert_eq!(multiplied_matrix.data[3], 83.0f32);
+    }
+
+    #[test]
+    fn integer_matrix_operations() {
+        let matrix_a: Matrix<i32> = Matrix::new(2, 2, vec![1, 10, 4, 9]);
+        let matrix_b: Matrix<i32> = Matrix::new(2, 2, vec![3, 5, 5, 7]);
+        let multiplied_matrix = matrix_a.multiply(&matrix_b, 16);
+        let subtracted_matrix = matrix_a.subtract(&matrix_b, 16);
+
+        assert_eq!(multiplied_matrix.data[0], 53);
+        assert_eq!(multiplied_matrix.data[3], 83);
+        assert_eq!(subtracted_matrix.data[0], -2);
+        assert_eq!(subtracted_matrix.data[3], 2);
+        assert_eq!(Matrix::<u64>::identity(2).data[3], 1);
+    }
+
     #[test]
     #[should_panic]
     fn matrix_addition_should_fail_when_matrices_are_not_same_dimensions() {
@@ -212,6 +310,34 @@ mod linear_algebra_tests {
         assert_eq!(hadamard_product_matrix.data[1], 20.0);
     }
 
+    #[test]
+    fn complex_conjugate_transpose() {
+        let a = vec![
+            Complex::new(1.0, 2.0),
+            Complex::new(0.0, -1.0),
+            Complex::new(3.0, 0.0),
+            Complex::new(2.0, 1.0),
+        ];
+        let matrix_a: Matrix<Complex<f64>> = Matrix::new(2, 2, a);
+
+        let conjugated_matrix = matrix_a.conjugate();
+        assert_eq!(conjugated_matrix.data[0], Complex::new(1.0, -2.0));
+        assert_eq!(conjugated_matrix.data[1], Complex::new(0.0, 1.0));
+
+        let adjoint_matrix = matrix_a.conjugate_transpose();
+        assert_eq!(adjoint_matrix.data[0], Complex::new(1.0, -2.0));
+        assert_eq!(adjoint_matrix.data[1], Complex::new(3.0, 0.0));
+        assert_eq!(adjoint_matrix.data[2], Complex::new(0.0, 1.0));
+        assert_eq!(adjoint_matrix.data[3], Complex::new(2.0, -1.0));
+
+        // A * Aᴴ é hermitiana
+        let multiplied_matrix = matrix_a.multiply(&adjoint_matrix, 16);
+        assert_eq!(multiplied_matrix.data[0], Complex::new(6.0, 0.0));
+        assert_eq!(multiplied_matrix.data[1], Complex::new(2.0, 4.0));
+        assert_eq!(multiplied_matrix.data[2], Complex::new(2.0, -4.0));
+        assert_eq!(multiplied_matrix.data[3], Complex::new(14.0, 0.0));
+    }
+
     #[test]
     fn transpose_matrix() {
         let a = vec![1.0, 10.0, 5.0, 4.0, 9.0, 3.0];
@@ -250,12 +376,11 @@ mod linear_algebra_tests {
         assert_eq!(matrix_a.data[8], 1.0);
     }
 
-
     #[test]
     fn apply_function_to_matrix() {
         let a = vec![1.0, 10.0, 4.0, 9.0];
         let matrix_a: Matrix = Matrix::new(2, 2, a);
-        let multiplied_by_2_matrix: Matrix = matrix_a.apply_function(&|x| (x * 2.0));
+        let multiplied_by_2_matrix: Matrix = matrix_a.apply_function(&|x| x * 2.0);
 
         assert_eq!(multiplied_by_2_matrix.rows, 2);
         assert_eq!(multiplied_by_2_matrix.cols, 2);
@@ -266,4 +391,848 @@ mod linear_algebra_tests {
         assert_eq!(multiplied_by_2_matrix.data[2], 8.0);
         assert_eq!(multiplied_by_2_matrix.data[3], 18.0);
     }
+
+    fn multiply_add_identity_transpose<M: MatrixOps>(a: &M, b: &M) -> M {
+        a.multiply(b).add(&M::identity(a.rows())).transpose()
+    }
+
+    #[test]
+    fn matrix_ops_trait() {
+        let matrix_a: Matrix = Matrix::new(2, 2, vec![1.0, 10.0, 4.0, 9.0]);
+        let matrix_b: Matrix = Matrix::new(2, 2, vec![3.0, 5.0, 5.0, 7.0]);
+        let result: Matrix = multiply_add_identity_transpose(&matrix_a, &matrix_b);
+
+        assert_eq!(MatrixOps::rows(&result), 2);
+        assert_eq!(MatrixOps::cols(&result), 2);
+
+        assert_eq!(result.get(0, 0), 54.0);
+        assert_eq!(result.get(0, 1), 57.0);
+        assert_eq!(result.get(1, 0), 75.0);
+        assert_eq!(result.get(1, 1), 84.0);
+    }
+
+    fn assert_close(a: &Matrix, b: &Matrix, tolerance: f64) {
+        assert_eq!((a.rows, a.cols), (b.rows, b.cols));
+        for (x, y) in a.data.iter().zip(&b.data) {
+            assert!((x - y).abs() < tolerance, "{} != {}", x, y);
+        }
+    }
+
+    #[test]
+    fn lu_reconstructs_permuted_matrix() {
+        let a: Matrix = Matrix::new(3, 3, vec![0.0, 2.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let lu = a.lu(2).unwrap();
+
+        let mut permuted = Vec::new();
+        for &row in lu.permutation() {
+            permuted.extend_from_slice(&a.data[row * 3..row * 3 + 3]);
+        }
+
+        let l = lu.l();
+        let u = lu.u();
+        for i in 0..3 {
+            assert_eq!(l.data[i * 3 + i], 1.0);
+            for j in 0..i {
+                assert_eq!(u.data[i * 3 + j], 0.0);
+                assert_eq!(l.data[j * 3 + i], 0.0);
+            }
+        }
+
+        assert_close(&l.multiply(&u, 16), &Matrix::new(3, 3, permuted), 1e-12);
+    }
+
+    #[test]
+    fn lu_determinant() {
+        let a: Matrix = Matrix::new(3, 3, vec![4.0, 3.0, 2.0, 2.0, 1.0, 3.0, 3.0, 2.0, 1.0]);
+        assert!((a.determinant(2).unwrap() - 3.0).abs() < 1e-12);
+
+        let swapped: Matrix = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(swapped.determinant(2).unwrap(), -1.0);
+    }
+
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let a: Matrix = Matrix::random(7, 7);
+        let inverse = a.inverse(2).unwrap();
+
+        assert_close(&a.multiply(&inverse, 16), &Matrix::identity(7), 1e-8);
+    }
+
+    #[test]
+    fn solve_linear_system() {
+        let a: Matrix = Matrix::random(7, 7);
+        let b: Matrix = Matrix::random(7, 2);
+        let x = a.solve(&b, 2).unwrap();
+
+        assert_close(&a.multiply(&x, 16), &b, 1e-8);
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let a: Matrix = Matrix::new(3, 3, vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0]);
+
+        assert_eq!(a.determinant(2).unwrap(), 0.0);
+        assert_eq!(a.inverse(2).err(), Some(MatrixError::Singular));
+        assert_eq!(
+            a.solve(&Matrix::from(vec![1.0, 2.0, 3.0]), 2).err(),
+            Some(MatrixError::Singular)
+        );
+    }
+
+    #[test]
+    fn lu_should_reject_non_square_matrix() {
+        let a: Matrix = Matrix::zeros(2, 3);
+
+        assert_eq!(
+            a.lu(2).err().map(|e| e.to_string()),
+            Some("Cannot factor a non-square matrix. 2x3".to_string())
+        );
+        assert_eq!(
+            a.inverse(2).err(),
+            Some(MatrixError::NotSquare {
+                op: "invert",
+                rows: 2,
+                cols: 3
+            })
+        );
+    }
+
+    #[test]
+    fn cholesky_of_known_matrix() {
+        let a: Matrix = Matrix::new(
+            3,
+            3,
+            vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        );
+        let expected: Matrix =
+            Matrix::new(3, 3, vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0]);
+
+        assert_close(&a.cholesky(2).unwrap(), &expected, 1e-12);
+    }
+
+    #[test]
+    fn cholesky_reconstructs_spd_matrix_and_solves() {
+        // M * Mᵀ + n * I é simétrica positiva definida
+        let m: Matrix = Matrix::random(9, 9);
+        let a = m
+            .multiply(&m.transpose(), 16)
+            .add(&Matrix::identity(9).apply_function(&|x| x * 9.0), 16);
+
+        let l = a.cholesky(2).unwrap();
+        assert_close(&l.multiply(&l.transpose(), 16), &a, 1e-9);
+
+        let b: Matrix = Matrix::random(9, 3);
+        let x = a.solve_spd(&b, 2).unwrap();
+        assert_close(&a.multiply(&x, 16), &b, 1e-9);
+    }
+
+    #[test]
+    fn cholesky_should_reject_indefinite_matrix() {
+        let a: Matrix = Matrix::new(2, 2, vec![1.0, 2.0, 2.0, 1.0]);
+
+        assert_eq!(
+            a.cholesky(2).err(),
+            Some(MatrixError::NotPositiveDefinite { pivot: 1 })
+        );
+        assert_eq!(
+            a.solve_spd(&Matrix::from(vec![1.0, 1.0]), 2).err(),
+            Some(MatrixError::NotPositiveDefinite { pivot: 1 })
+        );
+        assert!(matches!(
+            Matrix::<f64>::zeros(2, 3).cholesky(2),
+            Err(MatrixError::NotSquare { .. })
+        ));
+    }
+
+    #[test]
+    fn qr_reconstructs_matrix_with_orthonormal_q() {
+        for (rows, cols) in [(5, 5), (7, 4), (3, 5)] {
+            let a: Matrix = Matrix::random(rows, cols);
+            let qr = a.qr();
+            let q = qr.q();
+            let r = qr.r();
+            let p = rows.min(cols);
+
+            assert_eq!((q.rows, q.cols), (rows, p));
+            assert_eq!((r.rows, r.cols), (p, cols));
+            for i in 0..p {
+                for j in 0..i {
+                    assert_eq!(r.data[i * cols + j], 0.0);
+                }
+            }
+
+            assert_close(&q.multiply(&r, 16), &a, 1e-12);
+            assert_close(&q.transpose().multiply(&q, 16), &Matrix::identity(p), 1e-12);
+        }
+    }
+
+    #[test]
+    fn least_squares_fits_a_line() {
+        // y = 2x + 1 com ruído ortogonal às colunas, a reta continua a mesma
+        let a: Matrix = Matrix::new(4, 2, vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+        let b: Matrix = Matrix::from(vec![1.5, 2.5, 4.5, 7.5]);
+
+        let x = a.least_squares(&b).unwrap();
+        assert_close(&x, &Matrix::from(vec![1.0, 2.0]), 1e-12);
+    }
+
+    #[test]
+    fn least_squares_residual_is_orthogonal_to_columns() {
+        let a: Matrix = Matrix::random(10, 3);
+        let b: Matrix = Matrix::random(10, 2);
+        let x = a.least_squares(&b).unwrap();
+
+        let residual = a.multiply(&x, 16).subtract(&b, 16);
+        assert_close(
+            &a.transpose().multiply(&residual, 16),
+            &Matrix::zeros(3, 2),
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn least_squares_should_reject_underdetermined_and_rank_deficient_systems() {
+        let wide: Matrix = Matrix::random(2, 3);
+        assert_eq!(
+            wide.least_squares(&Matrix::from(vec![1.0, 2.0])).err(),
+            Some(MatrixError::Underdetermined { rows: 2, cols: 3 })
+        );
+
+        let repeated_column: Matrix = Matrix::new(3, 2, vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+        assert_eq!(
+            repeated_column
+                .least_squares(&Matrix::from(vec![1.0, 2.0, 3.0]))
+                .err(),
+            Some(MatrixError::Singular)
+        );
+    }
+
+    fn random_symmetric(n: usize) -> Matrix {
+        let m: Matrix = Matrix::random(n, n);
+        m.add(&m.transpose(), 16)
+    }
+
+    fn assert_eigen_decomposition(a: &Matrix) {
+        let n = a.rows;
+        let eigen = a.symmetric_eigen().unwrap();
+        let v = &eigen.vectors;
+
+        for i in 1..n {
+            assert!(eigen.values[i - 1] <= eigen.values[i]);
+        }
+
+        let mut diagonal: Matrix = Matrix::zeros(n, n);
+        for i in 0..n {
+            diagonal.data[i * n + i] = eigen.values[i];
+        }
+
+        let reconstructed = v.multiply(&diagonal, 16).multiply(&v.transpose(), 16);
+        assert_close(&reconstructed, a, 1e-10);
+        assert_close(&v.transpose().multiply(v, 16), &Matrix::identity(n), 1e-10);
+    }
+
+    #[test]
+    fn symmetric_eigen_of_known_matrix() {
+        let a: Matrix = Matrix::new(2, 2, vec![2.0, 1.0, 1.0, 2.0]);
+        let eigen = a.symmetric_eigen().unwrap();
+
+        assert!((eigen.values[0] - 1.0).abs() < 1e-12);
+        assert!((eigen.values[1] - 3.0).abs() < 1e-12);
+        assert_eigen_decomposition(&a);
+    }
+
+    #[test]
+    fn symmetric_eigen_small_uses_jacobi() {
+        assert_eigen_decomposition(&random_symmetric(6));
+    }
+
+    #[test]
+    fn symmetric_eigen_large_uses_tridiagonal_qr() {
+        assert_eigen_decomposition(&random_symmetric(40));
+    }
+
+    #[test]
+    fn symmetric_eigen_should_reject_non_symmetric_matrix() {
+        let a: Matrix = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(a.symmetric_eigen().err(), Some(MatrixError::NotSymmetric));
+    }
+
+    fn assert_svd(a: &Matrix, svd: &Svd) {
+        let u = &svd.u;
+        let vt = &svd.vt;
+
+        for pair in svd.singular_values.windows(2) {
+            assert!(pair[0] >= pair[1] && pair[1] >= 0.0);
+        }
+
+        let reconstructed = u.multiply(&svd.sigma(), 16).multiply(vt, 16);
+        assert_close(&reconstructed, a, 1e-10);
+        assert_close(
+            &u.transpose().multiply(u, 16),
+            &Matrix::identity(u.cols),
+            1e-10,
+        );
+        assert_close(
+            &vt.multiply(&vt.transpose(), 16),
+            &Matrix::identity(vt.rows),
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn svd_of_known_matrix() {
+        let a: Matrix = Matrix::new(2, 2, vec![3.0, 0.0, 4.0, 5.0]);
+        let svd = a.svd().unwrap();
+
+        assert!((svd.singular_values[0] - 45f64.sqrt()).abs() < 1e-12);
+        assert!((svd.singular_values[1] - 5f64.sqrt()).abs() < 1e-12);
+        assert!((a.condition_number().unwrap() - 3.0).abs() < 1e-12);
+        assert_svd(&a, &svd);
+    }
+
+    #[test]
+    fn svd_of_rectangular_matrices() {
+        for (rows, cols) in [(7, 4), (3, 6)] {
+            let a: Matrix = Matrix::random(rows, cols);
+            let p = rows.min(cols);
+
+            let thin = a.svd().unwrap();
+            assert_eq!((thin.u.rows, thin.u.cols), (rows, p));
+            assert_eq!((thin.vt.rows, thin.vt.cols), (p, cols));
+            assert_svd(&a, &thin);
+
+            let full = a.svd_full().unwrap();
+            assert_eq!((full.u.rows, full.u.cols), (rows, rows));
+            assert_eq!((full.vt.rows, full.vt.cols), (cols, cols));
+            assert_svd(&a, &full);
+
+            assert_eq!(a.rank(1e-10).unwrap(), p);
+        }
+    }
+
+    #[test]
+    fn svd_of_rank_deficient_matrix() {
+        // terceira coluna = primeira + segunda
+        let a: Matrix = Matrix::new(
+            4,
+            3,
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 9.0, 7.0, 8.0, 15.0, 1.0, 0.0, 1.0],
+        );
+
+        assert_eq!(a.rank(1e-10).unwrap(), 2);
+        assert_svd(&a, &a.svd().unwrap());
+        assert_svd(&a, &a.svd_full().unwrap());
+        assert!(a.condition_number().unwrap() > 1e12);
+
+        // condições de Moore-Penrose
+        let pinv = a.pseudo_inverse().unwrap();
+        assert_eq!((pinv.rows, pinv.cols), (3, 4));
+        assert_close(&a.multiply(&pinv, 16).multiply(&a, 16), &a, 1e-10);
+        assert_close(&pinv.multiply(&a, 16).multiply(&pinv, 16), &pinv, 1e-10);
+    }
+
+    #[test]
+    fn svd_of_ill_conditioned_matrix() {
+        // Hilbert 6x6, número de condição ~1.495e7
+        let n = 6;
+        let hilbert: Matrix = Matrix::new(
+            n,
+            n,
+            (0..n * n)
+                .map(|k| 1.0 / ((k / n + k % n + 1) as f64))
+                .collect(),
+        );
+
+        let condition = hilbert.condition_number().unwrap();
+        assert!(
+            (condition / 1.495105864e7 - 1.0).abs() < 1e-6,
+            "{}",
+            condition
+        );
+        assert_eq!(hilbert.rank(1e-10).unwrap(), n);
+        assert_svd(&hilbert, &hilbert.svd().unwrap());
+
+        let pinv = hilbert.pseudo_inverse().unwrap();
+        assert_close(&hilbert.multiply(&pinv, 16), &Matrix::identity(n), 1e-6);
+    }
+
+    #[test]
+    fn strassen_padded_size_halves_evenly_down_to_cutoff() {
+        assert_eq!(strassen::padded_size(64, 64), 64);
+        assert_eq!(strassen::padded_size(1000, 64), 1008);
+        assert_eq!(strassen::padded_size(1100, 128), 1104);
+        assert_eq!(strassen::padded_size(5, 1), 8);
+    }
+
+    #[test]
+    fn strassen_matches_classical_multiply() {
+        for (n, m, p, cutoff) in [(37, 37, 37, 8), (13, 29, 7, 4), (64, 64, 64, 16)] {
+            let a: Matrix = Matrix::random(n, m);
+            let b: Matrix = Matrix::random(m, p);
+
+            let expected = a.multiply(&b, 16);
+            let result = a.multiply_strassen(&b, cutoff);
+
+            assert_close(&result, &expected, 1e-12);
+        }
+    }
+
+    #[test]
+    fn strassen_is_exact_on_integers() {
+        let a: Matrix<i64> = Matrix::random(20, 20);
+        let b: Matrix<i64> = Matrix::random(20, 20);
+
+        assert_eq!(a.multiply_strassen(&b, 4).data, a.multiply(&b, 16).data);
+    }
+
+    #[test]
+    fn strassen_should_fail_when_dimensions_do_not_match() {
+        let a: Matrix = Matrix::zeros(2, 3);
+
+        assert_eq!(
+            a.try_multiply_strassen(&Matrix::zeros(2, 3), 8).err(),
+            Some(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (2, 3),
+                rhs: (2, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn every_loop_order_gives_the_same_product() {
+        let a: Matrix<i64> = Matrix::random(37, 29);
+        let b: Matrix<i64> = Matrix::random(29, 41);
+        let expected = a.multiply(&b, 8);
+
+        for loop_order in LoopOrder::ALL {
+            for block_size in [1, 8, 64] {
+                assert_eq!(
+                    a.multiply_with(&b, block_size, loop_order).data,
+                    expected.data
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn autotune_only_tries_blocks_that_fit_the_matrix() {
+        let measurements = autotune::<f64>(40, 1);
+
+        // 16 e 32 com as duas ordens
+        assert_eq!(measurements.len(), 4);
+        assert!(measurements.iter().all(|m| m.block_size <= 40));
+        assert!(measurements
+            .windows(2)
+            .all(|w| w[0].seconds <= w[1].seconds));
+        assert_eq!(autotune::<f32>(8, 1)[0].block_size, 8);
+    }
+
+    fn naive_transpose(matrix: &Matrix<i64>) -> Vec<i64> {
+        let mut data = Vec::with_capacity(matrix.rows * matrix.cols);
+        for j in 0..matrix.cols {
+            for i in 0..matrix.rows {
+                data.push(matrix.data[i * matrix.cols + j]);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn tiled_transpose_matches_naive_transpose() {
+        for (rows, cols) in [(1, 1), (1, 9), (9, 1), (16, 16), (37, 53), (64, 40)] {
+            let matrix: Matrix<i64> = Matrix::random(rows, cols);
+            let expected = naive_transpose(&matrix);
+
+            for block_size in [1, 7, 16, 100] {
+                let transposed = matrix.transpose_tiled(block_size);
+
+                assert_eq!((transposed.rows, transposed.cols), (cols, rows));
+                assert_eq!(transposed.data, expected);
+            }
+            assert_eq!(matrix.transpose().data, expected);
+        }
+    }
+
+    #[test]
+    fn in_place_transpose_matches_transpose() {
+        for (rows, cols) in [
+            (0, 5),
+            (1, 1),
+            (1, 9),
+            (9, 1),
+            (2, 3),
+            (45, 45),
+            (37, 53),
+            (64, 40),
+        ] {
+            let matrix: Matrix<i64> = Matrix::random(rows, cols);
+            let mut in_place = matrix.clone();
+            let data_ptr = in_place.data.as_ptr();
+
+            in_place.transpose_in_place();
+
+            assert_eq!((in_place.rows, in_place.cols), (cols, rows));
+            assert_eq!(in_place.data, naive_transpose(&matrix));
+            assert_eq!(in_place.data.as_ptr(), data_ptr);
+
+            in_place.transpose_in_place();
+            assert_eq!(in_place.data, matrix.data);
+        }
+    }
+
+    #[test]
+    fn assign_ops_reuse_the_destination_buffer() {
+        let a: Matrix<i64> = Matrix::random(13, 17);
+        let b: Matrix<i64> = Matrix::random(13, 17);
+        let mut c = a.clone();
+        let (ptr, capacity) = (c.data.as_ptr(), c.data.capacity());
+
+        c.add_assign(&b);
+        assert_eq!(c.data, a.add(&b, 4).data);
+        c.sub_assign(&b);
+        assert_eq!(c.data, a.data);
+        c.hadamard_assign(&b);
+        assert_eq!(c.data, a.hadamard_product(&b).data);
+        c.apply_function_mut(&|x| 2 * x + 1);
+        assert_eq!(
+            c.data,
+            a.hadamard_product(&b).apply_function(&|x| 2 * x + 1).data
+        );
+
+        assert_eq!((c.data.as_ptr(), c.data.capacity()), (ptr, capacity));
+        assert_eq!(
+            c.try_add_assign(&Matrix::zeros(17, 13)),
+            Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (13, 17),
+                rhs: (17, 13)
+            })
+        );
+        assert!(c.try_sub_assign(&Matrix::zeros(13, 1)).is_err());
+        assert!(c.try_hadamard_assign(&Matrix::zeros(1, 17)).is_err());
+    }
+
+    #[test]
+    fn multiply_into_scales_and_accumulates_in_place() {
+        let a: Matrix<i64> = Matrix::random(21, 34);
+        let b: Matrix<i64> = Matrix::random(34, 19);
+        let c0: Matrix<i64> = Matrix::random(21, 19);
+        let product = a.multiply(&b, 8);
+
+        for (alpha, beta) in [(1, 0), (1, 1), (2, 3), (-1, 1), (0, 5)] {
+            let mut c = c0.clone();
+            let (ptr, capacity) = (c.data.as_ptr(), c.data.capacity());
+
+            Matrix::multiply_into(&a, &b, alpha, beta, &mut c);
+
+            let expected: Vec<i64> = product
+                .data
+                .iter()
+                .zip(&c0.data)
+                .map(|(&ab, &c)| alpha * ab + beta * c)
+                .collect();
+            assert_eq!(c.data, expected);
+            assert_eq!((c.data.as_ptr(), c.data.capacity()), (ptr, capacity));
+        }
+
+        assert_eq!(
+            Matrix::try_multiply_into(&a, &b, 1, 0, &mut Matrix::zeros(19, 21)),
+            Err(MatrixError::DimensionMismatch {
+                op: "multiply into",
+                lhs: (21, 19),
+                rhs: (19, 21)
+            })
+        );
+        assert!(Matrix::try_multiply_into(&a, &a, 1, 0, &mut Matrix::zeros(21, 34)).is_err());
+    }
+
+    #[test]
+    fn multiply_into_with_zero_beta_ignores_nan_in_destination() {
+        let a: Matrix = Matrix::random(8, 5);
+        let b: Matrix = Matrix::random(5, 6);
+        let mut c = Matrix::new(8, 6, vec![f64::NAN; 48]);
+
+        Matrix::multiply_into(&a, &b, 0.5, 0.0, &mut c);
+
+        let expected = a.multiply(&b, 4).apply_function(&|x| 0.5 * x);
+        assert_close(&c, &expected, 1e-12);
+    }
+
+    #[test]
+    fn operators_match_the_methods() {
+        let a: Matrix<i64> = Matrix::random(6, 6);
+        let b: Matrix<i64> = Matrix::random(6, 6);
+        let sum = a.add(&b, 4);
+        let difference = a.subtract(&b, 4);
+        let product = a.multiply(&b, 4);
+
+        assert_eq!((&a + &b).data, sum.data);
+        assert_eq!((a.clone() + &b).data, sum.data);
+        assert_eq!((&a + b.clone()).data, sum.data);
+        assert_eq!((a.clone() + b.clone()).data, sum.data);
+
+        assert_eq!((&a - &b).data, difference.data);
+        assert_eq!((a.clone() - &b).data, difference.data);
+        assert_eq!((&a - b.clone()).data, difference.data);
+        assert_eq!((a.clone() - b.clone()).data, difference.data);
+
+        assert_eq!((&a * &b).data, product.data);
+        assert_eq!((a.clone() * &b).data, product.data);
+        assert_eq!((&a * b.clone()).data, product.data);
+        assert_eq!((a.clone() * b.clone()).data, product.data);
+
+        let doubled = a.apply_function(&|x| 2 * x);
+        assert_eq!((&a * 2).data, doubled.data);
+        assert_eq!((a.clone() * 2).data, doubled.data);
+        assert_eq!((2 * &a).data, doubled.data);
+        assert_eq!((-&a).data, a.apply_function(&|x| -x).data);
+        assert_eq!((-(-a.clone())).data, a.data);
+
+        let mut c = a.clone();
+        c += &b;
+        assert_eq!(c.data, sum.data);
+        c -= b.clone();
+        assert_eq!(c.data, a.data);
+        c *= &b;
+        assert_eq!(c.data, product.data);
+        c *= 3;
+        assert_eq!(c.data, product.apply_function(&|x| 3 * x).data);
+
+        let x: Matrix = Matrix::random(3, 4);
+        assert_eq!((0.5 * &x).data, (&x * 0.5).data);
+    }
+
+    #[test]
+    fn index_reads_and_writes_elements() {
+        let mut matrix: Matrix = Matrix::zeros(2, 3);
+        matrix[(1, 2)] = 4.5;
+        matrix[(0, 1)] += 1.0;
+
+        assert_eq!(matrix[(1, 2)], 4.5);
+        assert_eq!(matrix[(0, 1)], 1.0);
+        assert_eq!(matrix[(1, 2)], MatrixOps::get(&matrix, 1, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_rejects_a_column_past_the_end() {
+        let matrix: Matrix = Matrix::zeros(2, 3);
+        let _ = matrix[(0, 3)];
+    }
+
+    #[test]
+    #[should_panic(expected = "Dimension mismatch in add. 2x2 & 3x3")]
+    fn operators_panic_on_dimension_mismatch() {
+        let _ = &Matrix::<f64>::zeros(2, 2) + &Matrix::zeros(3, 3);
+    }
+
+    #[test]
+    fn operators_use_the_default_block_size() {
+        let a: Matrix = Matrix::random(30, 30);
+        let b: Matrix = Matrix::random(30, 30);
+
+        set_default_block_size(7);
+        assert_eq!(default_tile_sizes::<f64>().multiply, 7);
+        assert_eq!(tuning::<f64>().block_size, 7);
+        assert_close(&(&a * &b), &a.multiply(&b, 7), 1e-12);
+
+        set_default_block_size(0);
+        assert_ne!(default_tile_sizes::<f64>().add, 0);
+    }
+
+    #[test]
+    fn views_feed_add_and_multiply_without_copies() {
+        let a: Matrix<i64> = Matrix::random(9, 12);
+        let b: Matrix<i64> = Matrix::random(12, 7);
+
+        // bloco de A vezes bloco de B, e A vezes (Bᵀ)ᵀ
+        let block = Matrix::multiply_views(&a.submatrix(2..8, 1..11), &b.submatrix(1..11, 3..7));
+        let expected = Matrix::from_view(&a.submatrix(2..8, 1..11))
+            .multiply(&Matrix::from_view(&b.submatrix(1..11, 3..7)), 4);
+        assert_eq!(block.data, expected.data);
+        assert_eq!(
+            Matrix::multiply_views(&a.view(), &b.transposed().transpose()).data,
+            a.multiply(&b, 4).data
+        );
+
+        let sum = Matrix::add_views(
+            &a.submatrix(0..7, 0..7),
+            &b.transposed().submatrix(0..7, 0..7),
+        );
+        assert_eq!(sum[(3, 5)], a[(3, 5)] + b[(5, 3)]);
+        assert_eq!(a.row(4).to_vec(), a.data[48..60].to_vec());
+        assert_eq!(a.col(2).get(8, 0), a[(8, 2)]);
+
+        assert!(Matrix::try_add_views(&a.view(), &b.view()).is_err());
+        assert_eq!(
+            Matrix::try_multiply_views(&a.view(), &a.view()).err(),
+            Some(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (9, 12),
+                rhs: (9, 12)
+            })
+        );
+
+        let mut c: Matrix<i64> = Matrix::zeros(4, 4);
+        c.submatrix_mut(1..3, 1..3).fill(2);
+        assert_eq!(c.data, vec![0, 0, 0, 0, 0, 2, 2, 0, 0, 2, 2, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn sparse_round_trip_and_multiply_match_dense() {
+        // 300 linhas com cerca de um quinto das entradas diferentes de zero
+        let mut a: Matrix<i64> = Matrix::random(300, 40);
+        for (k, value) in a.data.iter_mut().enumerate() {
+            if k % 5 != 0 {
+                *value = 0;
+            }
+        }
+        let csr = a.to_csr();
+        assert!(csr.nnz() <= 300 * 40 / 5);
+        assert_eq!(Matrix::from_csr(&csr).data, a.data);
+        assert_eq!(Matrix::from_csc(&a.to_csc()).data, a.data);
+        assert_eq!(Matrix::from_csr(&csr.transpose()).data, a.transpose().data);
+
+        let x: Vec<i64> = (0..40).collect();
+        let y = sparse::spmv(&csr, &x);
+        assert_eq!(y, (&a * &Matrix::new(40, 1, x.clone())).data);
+        assert_eq!(y, csr.spmv(&x).unwrap());
+        assert!(sparse::try_spmv(&csr, &x[..39]).is_err());
+
+        let b: Matrix<i64> = Matrix::random(40, 9);
+        assert_eq!(Matrix::sparse_multiply(&csr, &b).data, (&a * &b).data);
+        assert_eq!(
+            Matrix::try_sparse_multiply(&csr, &a).err(),
+            Some(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (300, 40),
+                rhs: (300, 40)
+            })
+        );
+
+        let sum = csr.add(&a.transpose().transpose().to_csr()).unwrap();
+        assert_eq!(Matrix::from_csr(&sum).data, (&a + &a).data);
+    }
+
+    #[test]
+    fn matrix_market_round_trip() {
+        let a: Matrix<i64> = Matrix::random(5, 4);
+        let header = MarketHeader {
+            field: MarketField::Integer,
+            ..MarketHeader::coordinate()
+        };
+        let mut file = Vec::new();
+        a.to_matrix_market(&mut file, header).unwrap();
+        assert_eq!(
+            Matrix::<i64>::from_matrix_market(file.as_slice())
+                .unwrap()
+                .data,
+            a.data
+        );
+
+        let path = std::env::temp_dir().join(format!("{}_round_trip.mtx", env!("CARGO_PKG_NAME")));
+        let b: Matrix = Matrix::random(3, 6);
+        b.write_matrix_market(&path, MarketHeader::array()).unwrap();
+        let read = Matrix::<f64>::read_matrix_market(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((read.rows, read.cols), (3, 6));
+        assert_eq!(read.data, b.data);
+
+        let truncated = "%%MatrixMarket matrix array real general\n2 2\n1\n";
+        assert!(matches!(
+            Matrix::<f64>::from_matrix_market(truncated.as_bytes()),
+            Err(FormatError::Parse { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn npy_and_npz_round_trip() {
+        let a: Matrix = Matrix::random(7, 5);
+        let mut file = Vec::new();
+        a.to_npy(&mut file).unwrap();
+        let read = Matrix::<f64>::from_npy(file.as_slice()).unwrap();
+        assert_eq!((read.rows, read.cols), (7, 5));
+        assert_eq!(read.data, a.data);
+
+        // o mesmo fixture do matrix_core, salvo em ordem Fortran
+        let fortran = include_bytes!(
+            "../../../matrix_core/src/linear_algebra_tests/fixtures/fortran_3x2_f32.npy"
+        );
+        let b = Matrix::<f32>::from_npy(&fortran[..]).unwrap();
+        assert_eq!(b[(2, 1)], 5.0);
+        assert_eq!(b.data, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        let path = std::env::temp_dir().join(format!("{}_round_trip.npz", env!("CARGO_PKG_NAME")));
+        let c: Matrix<f32> = Matrix::random(2, 9);
+        Matrix::write_npz(&path, &[("b", &b), ("c", &c)]).unwrap();
+        let arrays = Matrix::<f32>::read_npz(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(arrays.len(), 2);
+        assert_eq!(arrays[0].0, "b");
+        assert_eq!(arrays[0].1.data, b.data);
+        assert_eq!((arrays[1].1.rows, arrays[1].1.cols), (2, 9));
+        assert_eq!(arrays[1].1.data, c.data);
+    }
+
+    #[test]
+    fn csv_round_trip_and_cell_errors() {
+        let a: Matrix = Matrix::random(4, 3);
+        let format = CsvFormat {
+            has_header: true,
+            ..CsvFormat::decimal_comma()
+        };
+        let mut file = Vec::new();
+        a.to_csv_writer(&mut file, format).unwrap();
+        let read = Matrix::<f64>::from_csv_reader(file.as_slice(), format).unwrap();
+        assert_eq!((read.rows, read.cols), (4, 3));
+        assert_eq!(read.data, a.data);
+
+        let path = std::env::temp_dir().join(format!("{}_round_trip.csv", env!("CARGO_PKG_NAME")));
+        a.write_csv(&path, CsvFormat::default()).unwrap();
+        let read = Matrix::<f64>::read_csv(&path, CsvFormat::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.data, a.data);
+
+        let b =
+            Matrix::<i64>::from_csv_reader("1;2\n3;4.000\n".as_bytes(), CsvFormat::decimal_comma())
+                .unwrap();
+        assert_eq!(b[(1, 1)], 4000);
+
+        let bad = "1,2\n3,4\n5,six\n";
+        assert!(matches!(
+            Matrix::<f64>::from_csv_reader(bad.as_bytes(), CsvFormat::default()),
+            Err(FormatError::Cell { row: 3, col: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn binary_round_trip_and_mapped_view() {
+        let a: Matrix = Matrix::random(5, 3);
+        let mut file = Vec::new();
+        a.to_binary(&mut file).unwrap();
+        let read = Matrix::<f64>::from_binary(file.as_slice()).unwrap();
+        assert_eq!((read.rows, read.cols), (5, 3));
+        assert_eq!(read.data, a.data);
+
+        let path = std::env::temp_dir().join(format!("{}_round_trip.bin", env!("CARGO_PKG_NAME")));
+        a.write_binary(&path).unwrap();
+        assert_eq!(Matrix::<f64>::read_binary(&path).unwrap().data, a.data);
+
+        let mapped = MappedMatrix::<f64>::open(&path).unwrap();
+        assert_eq!(Matrix::from_view(&mapped.view()).data, a.data);
+        let b: Matrix = Matrix::random(3, 4);
+        let product = Matrix::multiply_views(&mapped.view(), &b.view());
+        assert_eq!(
+            product.data,
+            Matrix::multiply_views(&a.view(), &b.view()).data
+        );
+        drop(mapped);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            Matrix::<f32>::from_binary(file.as_slice()),
+            Err(FormatError::InvalidHeader(_))
+        ));
+    }
 }
@@ -0,0 +1,9 @@
+// Operadores do `std::ops` para `Matrix`, gerados pela macro
+// `matrix_core::impl_contiguous_operators`. Eles chamam os métodos do
+// `MatrixOps`, então o `+` usa o bloco de `default_tile_sizes` e o `*`, o
+// bloco e a ordem dos loops de `tuning`, com as faixas de linhas em
+// paralelo.
+
+use crate::Matrix;
+
+matrix_core::impl_contiguous_operators!(Matrix);
@@ -0,0 +1,27 @@
+// SpMV paralela: cada tarefa calcula um trecho de `y`, lendo só as linhas
+// de A correspondentes, como as faixas de linhas do produto denso. Nenhuma
+// tarefa escreve fora do próprio trecho, então não há sincronização.
+
+use rayon::prelude::*;
+
+use crate::{CsrMatrix, MatrixError, Scalar};
+
+// linhas por tarefa, para que linhas com poucos não-zeros não virem uma
+// tarefa cada
+const MIN_ROWS_PER_TASK: usize = 256;
+
+/// `y = A * x`, split by rows between the rayon threads.
+pub fn spmv<T: Scalar>(a: &CsrMatrix<T>, x: &[T]) -> Vec<T> {
+    try_spmv(a, x).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_spmv<T: Scalar>(a: &CsrMatrix<T>, x: &[T]) -> Result<Vec<T>, MatrixError> {
+    a.check_vector(x)?;
+
+    let mut y = vec![T::zero(); a.rows()];
+    y.par_iter_mut()
+        .with_min_len(MIN_ROWS_PER_TASK)
+        .enumerate()
+        .for_each(|(i, y_i)| *y_i = a.row_dot(i, x));
+    Ok(y)
+}
@@ -0,0 +1,40 @@
+// Strassen-Winograd de `matrix_core::strassen`, com a recursão própria: os
+// sete produtos de cada nível são independentes e rodam em paralelo com
+// `rayon::join`. As folhas usam o kernel tiled paralelo deste crate com
+// `DEFAULT_BLOCK_SIZE`.
+
+use matrix_core::{strassen, Scalar};
+
+use crate::{multiply_tiled, LoopOrder, DEFAULT_BLOCK_SIZE};
+
+pub(crate) use matrix_core::strassen::{pad, padded_size, unpad};
+
+/// `a * b` for `s x s` matrices, where `s` comes from `padded_size`.
+pub(crate) fn multiply<T: Scalar>(a: &[T], b: &[T], s: usize, cutoff: usize) -> Vec<T> {
+    if !strassen::splits(s, cutoff) {
+        let mut c = vec![T::zero(); s * s];
+        multiply_tiled(a, b, &mut c, s, s, s, DEFAULT_BLOCK_SIZE, LoopOrder::Ijk);
+        return c;
+    }
+
+    let h = s / 2;
+    let [f1, f2, f3, f4, f5, f6, f7] = strassen::factors(a, b, s);
+    let product = |(x, y): &(Vec<T>, Vec<T>)| multiply(x, y, h, cutoff);
+
+    let ((p1, p2), ((p3, p4), ((p5, p6), p7))) = rayon::join(
+        || rayon::join(|| product(&f1), || product(&f2)),
+        || {
+            rayon::join(
+                || rayon::join(|| product(&f3), || product(&f4)),
+                || {
+                    rayon::join(
+                        || rayon::join(|| product(&f5), || product(&f6)),
+                        || product(&f7),
+                    )
+                },
+            )
+        },
+    );
+
+    strassen::combine(&[p1, p2, p3, p4, p5, p6, p7], h)
+}
@@ -0,0 +1,103 @@
+// Transposta em blocos: dentro de um bloco `block_size x block_size` a
+// leitura anda pela linha de `src` e a escrita salta `rows` elementos, mas as
+// poucas linhas de `dst` tocadas pelo bloco continuam no cache até ele
+// terminar. Sem blocos, cada escrita cai numa linha de cache diferente.
+//
+// A versão in-place de matrizes retangulares segue os ciclos da permutação
+// `k -> k * rows mod (rows * cols - 1)`, que leva a posição de `A[i][j]` para
+// a de `Aᵀ[j][i]`. As versões in-place são sequenciais: cada troca mexe em
+// duas regiões distantes do vetor, que não dá para dividir entre as threads
+// com `par_chunks_mut`.
+
+use matrix_core::Scalar;
+use rayon::prelude::*;
+
+/// `dst = srcᵀ` for a row-major `rows x cols` source. Each band of
+/// `block_size` rows of `dst` (columns of `src`) is filled by one task, tile
+/// by tile, so the tasks never write to the same memory.
+pub(crate) fn tiled<T: Scalar>(
+    src: &[T],
+    dst: &mut [T],
+    rows: usize,
+    cols: usize,
+    block_size: usize,
+) {
+    let block_size = block_size.max(1);
+    if rows == 0 || cols == 0 {
+        return;
+    }
+
+    dst.par_chunks_mut(block_size * rows)
+        .enumerate()
+        .for_each(|(band, dst_band)| {
+            let jj = band * block_size;
+            let j_max = (jj + block_size).min(cols);
+
+            for ii in (0..rows).step_by(block_size) {
+                let i_max = (ii + block_size).min(rows);
+
+                for i in ii..i_max {
+                    for j in jj..j_max {
+                        dst_band[(j - jj) * rows + i] = src[i * cols + j];
+                    }
+                }
+            }
+        });
+}
+
+/// Transposes an `n x n` matrix in place, swapping each tile above the
+/// diagonal with its mirror below it.
+pub(crate) fn square_in_place<T: Scalar>(data: &mut [T], n: usize, block_size: usize) {
+    let block_size = block_size.max(1);
+
+    for ii in (0..n).step_by(block_size) {
+        let i_max = (ii + block_size).min(n);
+
+        for jj in (ii..n).step_by(block_size) {
+            let j_max = (jj + block_size).min(n);
+
+            for i in ii..i_max {
+                // no bloco da diagonal só o triângulo de cima
+                let j_start = if ii == jj { i + 1 } else { jj };
+
+                for j in j_start..j_max {
+                    data.swap(i * n + j, j * n + i);
+                }
+            }
+        }
+    }
+}
+
+/// Transposes a row-major `rows x cols` matrix in place by following the
+/// cycles of the permutation. Uses one bit per element to mark the visited
+/// positions.
+pub(crate) fn in_place<T: Scalar>(data: &mut [T], rows: usize, cols: usize) {
+    let len = rows * cols;
+    if len < 3 {
+        return;
+    }
+
+    // a primeira e a última posição nunca mudam
+    let modulus = len - 1;
+    let mut visited = vec![0u64; len.div_ceil(64)];
+
+    for start in 1..modulus {
+        if visited[start / 64] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+
+        let mut value = data[start];
+        let mut position = start;
+
+        loop {
+            let next = position * rows % modulus;
+            std::mem::swap(&mut value, &mut data[next]);
+            visited[next / 64] |= 1 << (next % 64);
+            position = next;
+
+            if position == start {
+                break;
+            }
+        }
+    }
+}
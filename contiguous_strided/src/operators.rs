@@ -0,0 +1,7 @@
+// Operadores do `std::ops` para `Matrix`, gerados pela macro
+// `matrix_core::impl_contiguous_operators`. Eles chamam os métodos do
+// `MatrixOps`, então o `+` e o `*` usam os loops strided deste crate.
+
+use crate::Matrix;
+
+matrix_core::impl_contiguous_operators!(Matrix);
@@ -0,0 +1,59 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use contiguous_tiled::Matrix;
+
+// Tempo das transpostas para vários tamanhos, melhor de 3 execuções. 1024 e
+// 2048 são potências de 2, o pior caso do loop duplo: cada escrita cai no
+// mesmo conjunto do cache.
+
+const SIZES: [usize; 6] = [256, 1000, 1024, 2000, 2048, 4096];
+
+fn best_ms(mut run: impl FnMut()) -> f64 {
+    (0..3)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed().as_secs_f64() * 1000.0
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn naive(matrix: &Matrix) -> Vec<f64> {
+    let mut buffer = vec![0.0; matrix.rows * matrix.cols];
+
+    for i in 0..matrix.rows {
+        for j in 0..matrix.cols {
+            buffer[j * matrix.rows + i] = matrix.data[i * matrix.cols + j];
+        }
+    }
+
+    buffer
+}
+
+fn main() {
+    println!("| n | loop duplo (ms) | tiled (ms) | recursiva (ms) | in-place (ms) | in-place n x 2n (ms) |");
+    println!("| ---: | ---: | ---: | ---: | ---: | ---: |");
+
+    for &size in &SIZES {
+        let matrix: Matrix = Matrix::random(size, size);
+        let mut square = matrix.clone();
+        let mut rectangular: Matrix = Matrix::random(size, 2 * size);
+
+        println!(
+            "| {} | {:.1} | {:.1} | {:.1} | {:.1} | {:.1} |",
+            size,
+            best_ms(|| {
+                black_box(naive(&matrix));
+            }),
+            best_ms(|| {
+                black_box(matrix.transpose());
+            }),
+            best_ms(|| {
+                black_box(matrix.transpose_recursive());
+            }),
+            best_ms(|| square.transpose_in_place()),
+            best_ms(|| rectangular.transpose_in_place()),
+        );
+    }
+}
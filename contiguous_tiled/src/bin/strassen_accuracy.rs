@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use contiguous_tiled::{Matrix, DEFAULT_BLOCK_SIZE};
+
+// Compara `multiply_strassen` com o kernel clássico `multiply`. O erro é
+// relativo ao maior elemento de A * B. Os elementos aleatórios estão em
+// [-1, 1), com média zero, então as parcelas de cada elemento de A * B se
+// cancelam e ele cresce só com √n; já as somas de quadrantes do Strassen
+// (S e T) podem dobrar de magnitude a cada nível, e é isso que faz o erro
+// relativo crescer com n e cair com cutoffs maiores.
+
+const SIZES: [usize; 5] = [64, 127, 256, 500, 1024];
+const CUTOFFS: [usize; 3] = [32, 64, 128];
+
+fn main() {
+    println!(
+        "| n | cutoff | erro máx. absoluto | erro máx. relativo | clássico (ms) | strassen (ms) |"
+    );
+    println!("| ---: | ---: | ---: | ---: | ---: | ---: |");
+
+    for &size in &SIZES {
+        let a: Matrix = Matrix::random(size, size);
+        let b: Matrix = Matrix::random(size, size);
+
+        let start = Instant::now();
+        let classical = a.multiply(&b, DEFAULT_BLOCK_SIZE);
+        let classical_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let scale = classical
+            .data
+            .iter()
+            .fold(0.0f64, |max, x| max.max(x.abs()));
+
+        for &cutoff in &CUTOFFS {
+            let start = Instant::now();
+            let strassen = a.multiply_strassen(&b, cutoff);
+            let strassen_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+            let max_error = classical
+                .data
+                .iter()
+                .zip(&strassen.data)
+                .fold(0.0f64, |max, (x, y)| max.max((x - y).abs()));
+
+            println!(
+                "| {} | {} | {:.3e} | {:.3e} | {:.1} | {:.1} |",
+                size,
+                cutoff,
+                max_error,
+                max_error / scale,
+                classical_ms,
+                strassen_ms
+            );
+        }
+    }
+}
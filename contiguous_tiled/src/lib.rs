@@ -1,21 +1,121 @@
-use rand::Rng;
+mod io;
+mod linalg;
+#[allow(clippy::module_inception)]
 mod linear_algebra_tests;
+mod operators;
+mod recursive;
+mod strassen;
+mod transpose;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
+use std::hint::black_box;
+use std::ops::{Neg, Range};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+pub use linalg::{Lu, Qr, Svd, SymmetricEigen};
+pub use matrix_core::cache::{CacheLevel, CacheTopology, TileSizes};
+pub use matrix_core::io::binary::{BinaryElement, MappedMatrix};
+pub use matrix_core::io::csv::CsvFormat;
+pub use matrix_core::io::market::{MarketField, MarketFormat, MarketHeader, MarketSymmetry};
+pub use matrix_core::io::npy::NpyElement;
+pub use matrix_core::io::FormatError;
+pub use matrix_core::tuning::{LoopOrder, Measurement, Tuning};
+use matrix_core::view;
+use matrix_core::{cache, gemm};
+pub use matrix_core::{
+    Complex, CooMatrix, CscMatrix, CsrMatrix, MatrixError, MatrixOps, MatrixView, MatrixViewMut,
+    Real, Scalar,
+};
+
+/// Block size of the committed benchmark results, also the base case of
+/// `multiply_strassen`.
+pub const DEFAULT_BLOCK_SIZE: usize = 264;
+
+static BLOCK_SIZE_OVERRIDE: AtomicUsize = AtomicUsize::new(0);
+
+/// Block size used from now on, in every thread, whenever none is passed:
+/// by [`MatrixOps`], the operators (`&a * &b`, `&a + &b`...) and `transpose`.
+/// It wins over the tuned and cache-derived sizes; `0` goes back to those.
+pub fn set_default_block_size(block_size: usize) {
+    BLOCK_SIZE_OVERRIDE.store(block_size, Ordering::Relaxed);
+}
+
+/// Tile sizes used when no block size is passed: all equal to the value of
+/// `set_default_block_size`, or derived from the detected cache sizes.
+pub fn default_tile_sizes<T>() -> TileSizes {
+    match BLOCK_SIZE_OVERRIDE.load(Ordering::Relaxed) {
+        0 => cache::topology().tile_sizes::<T>(),
+        block_size => TileSizes {
+            multiply: block_size,
+            add: block_size,
+            subtract: block_size,
+            transpose: block_size,
+        },
+    }
+}
+
+/// Block size and loop order saved by the autotuner for this crate and `T`
+/// (see `matrix_core::tuning`). When nothing was tuned for `T`, the block
+/// size comes from `default_tile_sizes` and the order is `ijk`;
+/// `set_default_block_size` replaces the tuned block size too. The config
+/// file is read once per process; this is what [`MatrixOps::multiply`], the
+/// operators and `multiply_into` use.
+pub fn tuning<T>() -> Tuning {
+    static SAVED: OnceLock<BTreeMap<String, Tuning>> = OnceLock::new();
+
+    let saved = SAVED
+        .get_or_init(matrix_core::tuning::load_all)
+        .get(&matrix_core::tuning::section::<T>(env!("CARGO_PKG_NAME")))
+        .copied();
+
+    match saved {
+        Some(tuning) if BLOCK_SIZE_OVERRIDE.load(Ordering::Relaxed) == 0 => tuning,
+        _ => Tuning {
+            block_size: default_tile_sizes::<T>().multiply,
+            loop_order: saved.map_or(LoopOrder::Ijk, |tuning| tuning.loop_order),
+        },
+    }
+}
+
+/// Times `multiply_with` on random `size x size` matrices of `T` for every
+/// block size candidate up to `size` and every loop order, fastest first.
+pub fn autotune<T: Scalar>(size: usize, repetitions: usize) -> Vec<Measurement> {
+    let a: Matrix<T> = Matrix::random(size, size);
+    let b: Matrix<T> = Matrix::random(size, size);
+
+    let mut block_sizes: Vec<usize> = matrix_core::tuning::BLOCK_SIZE_CANDIDATES
+        .into_iter()
+        .filter(|&block_size| block_size <= size)
+        .collect();
+    if block_sizes.is_empty() {
+        block_sizes.push(size.max(1));
+    }
+
+    matrix_core::tuning::sweep(
+        &block_sizes,
+        &LoopOrder::ALL,
+        repetitions,
+        |block_size, loop_order| {
+            black_box(a.multiply_with(&b, block_size, loop_order));
+        },
+    )
+}
 
 #[derive(Clone, Serialize, Deserialize)]
-pub struct Matrix {
+pub struct Matrix<T = f64> {
     pub rows: usize,
     pub cols: usize,
-    pub data: Vec<f64>,
+    pub data: Vec<T>,
 }
 
-impl Matrix {
-    pub fn random(rows: usize, cols: usize) -> Matrix {
-        let mut buffer = Vec::<f64>::with_capacity(rows * cols);
+impl<T: Scalar> Matrix<T> {
+    pub fn random(rows: usize, cols: usize) -> Matrix<T> {
+        let mut buffer = Vec::<T>::with_capacity(rows * cols);
 
         for _ in 0..rows * cols {
-            let num = rand::thread_rng().gen::<f64>() * 2.0 - 1.0;
+            let num = T::random();
 
             buffer.push(num);
         }
@@ -27,31 +127,47 @@ impl Matrix {
         }
     }
 
-    pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Matrix {
-        assert!(data.len() - 1 != rows * cols, "Invalid Size");
-        Matrix { rows, cols, data }
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
+        Matrix::try_new(rows, cols, data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(rows: usize, cols: usize, data: Vec<T>) -> Result<Matrix<T>, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::InvalidDataLength {
+                expected: rows * cols,
+                actual: data.len(),
+            });
+        }
+
+        Ok(Matrix { rows, cols, data })
     }
 
-    pub fn zeros(rows: usize, cols: usize) -> Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
         Matrix {
             rows,
             cols,
-            data: vec![0.0; cols * rows],
+            data: vec![T::zero(); cols * rows],
         }
     }
 
-    pub fn add(&self, other: &Matrix, block_size: usize) -> Matrix {
+    pub fn add(&self, other: &Matrix<T>, block_size: usize) -> Matrix<T> {
+        self.try_add(other, block_size)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add(&self, other: &Matrix<T>, block_size: usize) -> Result<Matrix<T>, MatrixError> {
         if self.rows != other.rows || self.cols != other.cols {
-            panic!(
-                "Cannot add matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
         let rows = self.rows;
         let cols = self.cols;
 
-        let mut result_data = vec![0.0; rows * cols];
+        let mut result_data = vec![T::zero(); rows * cols];
 
         for ii in (0..rows).step_by(block_size) {
             for jj in (0..cols).step_by(block_size) {
@@ -59,31 +175,45 @@ impl Matrix {
                 let j_max = (jj + block_size).min(cols);
 
                 for i in ii..i_max {
-                    for j in jj..j_max {
-                        let idx = i * cols + j;
-                        result_data[idx] = self.data[idx] + other.data[idx];
-                    }
+                    let row = i * cols + jj..i * cols + j_max;
+                    T::add_slices(
+                        &self.data[row.clone()],
+                        &other.data[row.clone()],
+                        &mut result_data[row],
+                    );
                 }
             }
         }
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
     }
 
-    pub fn subtract(&self, other: &Matrix, block_size: usize) -> Matrix {
-        assert!(
-            self.rows == other.rows && self.cols == other.cols,
-            "Cannot subtract matrices with different dimensions"
-        );
+    pub fn subtract(&self, other: &Matrix<T>, block_size: usize) -> Matrix<T> {
+        self.try_subtract(other, block_size)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_subtract(
+        &self,
+        other: &Matrix<T>,
+        block_size: usize,
+    ) -> Result<Matrix<T>, MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "subtract",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
 
         let rows = self.rows;
         let cols = self.cols;
 
-        let mut result_data = vec![0.0; rows * cols];
+        let mut result_data = vec![T::zero(); rows * cols];
 
         for ii in (0..rows).step_by(block_size) {
             for jj in (0..cols).step_by(block_size) {
@@ -91,95 +221,253 @@ impl Matrix {
                 let j_max = (jj + block_size).min(cols);
 
                 for i in ii..i_max {
-                    for j in jj..j_max {
-                        let idx = i * cols + j;
-                        result_data[idx] = self.data[idx] - other.data[idx];
-                    }
+                    let row = i * cols + jj..i * cols + j_max;
+                    T::sub_slices(
+                        &self.data[row.clone()],
+                        &other.data[row.clone()],
+                        &mut result_data[row],
+                    );
                 }
             }
         }
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
+        })
+    }
+
+    pub fn multiply(&self, other: &Matrix<T>, block_size: usize) -> Matrix<T> {
+        self.try_multiply(other, block_size)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multiply(
+        &self,
+        other: &Matrix<T>,
+        block_size: usize,
+    ) -> Result<Matrix<T>, MatrixError> {
+        self.try_multiply_with(other, block_size, LoopOrder::Ijk)
+    }
+
+    /// `multiply` with the order of the loops inside each tile chosen by the
+    /// caller, as swept by the autotuner.
+    pub fn multiply_with(
+        &self,
+        other: &Matrix<T>,
+        block_size: usize,
+        loop_order: LoopOrder,
+    ) -> Matrix<T> {
+        self.try_multiply_with(other, block_size, loop_order)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multiply_with(
+        &self,
+        other: &Matrix<T>,
+        block_size: usize,
+        loop_order: LoopOrder,
+    ) -> Result<Matrix<T>, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
+
+        let mut result_data = vec![T::zero(); self.rows * other.cols];
+
+        multiply_tiled(
+            &self.data,
+            &other.data,
+            &mut result_data,
+            self.rows,
+            self.cols,
+            other.cols,
+            block_size,
+            loop_order,
+        );
+
+        Ok(Matrix {
+            rows: self.rows,
+            cols: other.cols,
+            data: result_data,
+        })
+    }
+
+    /// Same result as `multiply`, computed by the packed, register-blocked
+    /// kernel from `matrix_core::gemm`.
+    pub fn multiply_packed(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_multiply_packed(other)
+            .unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn multiply(&self, other: &Matrix, block_size: usize) -> Matrix {
+    pub fn try_multiply_packed(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.cols != other.rows {
-            panic!(
-                "Cannot multiply matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![0.0; self.rows * other.cols];
-        let n = self.rows;
-        let m = self.cols;
-        let p = other.cols;
+        let mut result_data = vec![T::zero(); self.rows * other.cols];
+
+        gemm::gemm(
+            self.rows,
+            other.cols,
+            self.cols,
+            &self.data,
+            self.cols,
+            &other.data,
+            other.cols,
+            &mut result_data,
+            other.cols,
+        );
+
+        Ok(Matrix {
+            rows: self.rows,
+            cols: other.cols,
+            data: result_data,
+        })
+    }
+
+    /// Strassen-Winograd multiply: splits recursively into quadrants (padding
+    /// with zeros when the size does not halve evenly) and uses the tiled
+    /// kernel, with `DEFAULT_BLOCK_SIZE`, once a quadrant is at most `cutoff`.
+    pub fn multiply_strassen(&self, other: &Matrix<T>, cutoff: usize) -> Matrix<T> {
+        self.try_multiply_strassen(other, cutoff)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        for ii in (0..n).step_by(block_size) {
-            for jj in (0..p).step_by(block_size) {
-                for kk in (0..m).step_by(block_size) {
-                    // para cada bloco
-                    let i_max = (ii + block_size).min(n);
-                    let j_max = (jj + block_size).min(p);
-                    let k_max = (kk + block_size).min(m);
+    pub fn try_multiply_strassen(
+        &self,
+        other: &Matrix<T>,
+        cutoff: usize,
+    ) -> Result<Matrix<T>, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
 
-                    for i in ii..i_max {
-                        for j in jj..j_max {
-                            let mut sum = result_data[i * p + j];
+        let s = strassen::padded_size(self.rows.max(self.cols).max(other.cols), cutoff);
+        let a = strassen::pad(&self.data, self.rows, self.cols, s);
+        let b = strassen::pad(&other.data, other.rows, other.cols, s);
+        let c = strassen::multiply(&a, &b, s, cutoff.max(1));
 
-                            for k in kk..k_max {
-                                sum += self.data[i * m + k] * other.data[k * p + j];
-                            }
+        Ok(Matrix {
+            rows: self.rows,
+            cols: other.cols,
+            data: strassen::unpad(&c, s, self.rows, other.cols),
+        })
+    }
 
-                            result_data[i * p + j] = sum;
-                        }
-                    }
-                }
-            }
+    /// Cache-oblivious multiply: halves the largest of the three dimensions
+    /// recursively, so no block size has to be chosen.
+    pub fn multiply_recursive(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_multiply_recursive(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multiply_recursive(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        Matrix {
+        let mut result_data = vec![T::zero(); self.rows * other.cols];
+
+        recursive::multiply(
+            &self.data,
+            &other.data,
+            &mut result_data,
+            self.cols,
+            other.cols,
+            (0, self.rows),
+            (0, self.cols),
+            (0, other.cols),
+        );
+
+        Ok(Matrix {
             rows: self.rows,
             cols: other.cols,
             data: result_data,
-        }
+        })
+    }
+
+    pub fn hadamard_product(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_hadamard_product(other)
+            .unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn hadamard_product(&self, other: &Matrix) -> Matrix {
+    pub fn try_hadamard_product(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.rows != other.rows || self.cols != other.cols {
-            panic!(
-                "Cannot apply hadamard product to matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "hadamard product",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![0.0; self.rows * self.cols];
+        let mut result_data = vec![T::zero(); self.rows * self.cols];
 
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                result_data[i * self.cols + j] =
-                    self.data[i * self.cols + j] * other.data[i * self.cols + j];
-            }
-        }
+        T::mul_slices(&self.data, &other.data, &mut result_data);
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
     }
 
-    pub fn transpose(&self) -> Matrix {
-        let mut buffer = vec![0.0; self.cols * self.rows];
+    /// Tiled transpose with the block size derived from the L1 size.
+    pub fn transpose(&self) -> Matrix<T> {
+        self.transpose_tiled(default_tile_sizes::<T>().transpose)
+    }
 
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                buffer[j * self.rows + i] = self.data[i * self.cols + j];
-            }
+    pub fn transpose_tiled(&self, block_size: usize) -> Matrix<T> {
+        let mut buffer = vec![T::zero(); self.cols * self.rows];
+
+        transpose::tiled(&self.data, &mut buffer, self.rows, self.cols, block_size);
+
+        Matrix {
+            rows: self.cols,
+            cols: self.rows,
+            data: buffer,
         }
+    }
+
+    /// Transposes without a second buffer: tile by tile for square matrices,
+    /// following the cycles of the permutation otherwise.
+    pub fn transpose_in_place(&mut self) {
+        if self.rows == self.cols {
+            let block_size = default_tile_sizes::<T>().transpose;
+            transpose::square_in_place(&mut self.data, self.rows, block_size);
+        } else {
+            transpose::in_place(&mut self.data, self.rows, self.cols);
+            std::mem::swap(&mut self.rows, &mut self.cols);
+        }
+    }
+
+    /// Cache-oblivious transpose, halving the larger dimension recursively.
+    pub fn transpose_recursive(&self) -> Matrix<T> {
+        let mut buffer = vec![T::zero(); self.cols * self.rows];
+
+        recursive::transpose(
+            &self.data,
+            &mut buffer,
+            self.rows,
+            self.cols,
+            (0, self.rows),
+            (0, self.cols),
+        );
 
         Matrix {
             rows: self.cols,
@@ -188,13 +476,13 @@ impl Matrix {
         }
     }
 
-    pub fn identity(size: usize) -> Matrix {
-        let mut result_data = vec![0.0; size * size];
+    pub fn identity(size: usize) -> Matrix<T> {
+        let mut result_data = vec![T::zero(); size * size];
 
         for i in 0..size {
             for j in 0..size {
                 if j.eq(&i) {
-                    result_data[i * size + j] = 1.0;
+                    result_data[i * size + j] = T::one();
                 }
             }
         }
@@ -206,17 +494,437 @@ impl Matrix {
         }
     }
 
-    pub fn apply_function(&self, func: &dyn Fn(f64) -> f64) -> Matrix {
-        let a: Vec<f64> = self.data.iter().map(|&val| func(val)).collect();
-        return Matrix {
+    pub fn apply_function(&self, func: &dyn Fn(T) -> T) -> Matrix<T> {
+        let a: Vec<T> = self.data.iter().map(|&val| func(val)).collect();
+        Matrix {
             cols: self.cols,
             rows: self.rows,
             data: a,
-        };
+        }
+    }
+
+    /// `self += other` in place, without allocating.
+    pub fn add_assign(&mut self, other: &Matrix<T>) {
+        self.try_add_assign(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add_assign(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        T::add_assign_slices(&mut self.data, &other.data);
+
+        Ok(())
+    }
+
+    /// `self -= other` in place.
+    pub fn sub_assign(&mut self, other: &Matrix<T>) {
+        self.try_sub_assign(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_sub_assign(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "subtract",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        T::sub_assign_slices(&mut self.data, &other.data);
+
+        Ok(())
+    }
+
+    /// Element-wise `self *= other` in place.
+    pub fn hadamard_assign(&mut self, other: &Matrix<T>) {
+        self.try_hadamard_assign(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_hadamard_assign(&mut self, other: &Matrix<T>) -> Result<(), MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "hadamard product",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        T::mul_assign_slices(&mut self.data, &other.data);
+
+        Ok(())
+    }
+
+    /// `apply_function` writing over the elements of `self`.
+    pub fn apply_function_mut(&mut self, func: &dyn Fn(T) -> T) {
+        for x in self.data.iter_mut() {
+            *x = func(*x);
+        }
+    }
+
+    /// GEMM-style `c = alpha * a * b + beta * c`, written into the existing
+    /// buffer of `c`, with the block size and loop order of [`tuning`].
+    pub fn multiply_into(a: &Matrix<T>, b: &Matrix<T>, alpha: T, beta: T, c: &mut Matrix<T>) {
+        Matrix::try_multiply_into(a, b, alpha, beta, c).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multiply_into(
+        a: &Matrix<T>,
+        b: &Matrix<T>,
+        alpha: T,
+        beta: T,
+        c: &mut Matrix<T>,
+    ) -> Result<(), MatrixError> {
+        if a.cols != b.rows {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (a.rows, a.cols),
+                rhs: (b.rows, b.cols),
+            });
+        }
+        if c.rows != a.rows || c.cols != b.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply into",
+                lhs: (a.rows, b.cols),
+                rhs: (c.rows, c.cols),
+            });
+        }
+
+        // com beta = 0 o C antigo é ignorado, mesmo se tiver NaN
+        if beta == T::zero() {
+            c.data.fill(T::zero());
+        } else if beta != T::one() {
+            for x in c.data.iter_mut() {
+                *x = beta * *x;
+            }
+        }
+
+        if alpha == T::zero() {
+            return Ok(());
+        }
+
+        // com alpha = 1 o kernel acumula direto em C; senão o produto vai
+        // para um buffer e alpha é aplicado uma vez por elemento, fora dos
+        // loops dos blocos
+        let tuning = tuning::<T>();
+        let (block_size, loop_order) = (tuning.block_size, tuning.loop_order);
+        if alpha == T::one() {
+            multiply_tiled(
+                &a.data,
+                &b.data,
+                &mut c.data,
+                a.rows,
+                a.cols,
+                b.cols,
+                block_size,
+                loop_order,
+            );
+        } else {
+            let mut product = vec![T::zero(); c.data.len()];
+            multiply_tiled(
+                &a.data,
+                &b.data,
+                &mut product,
+                a.rows,
+                a.cols,
+                b.cols,
+                block_size,
+                loop_order,
+            );
+            for (x, &y) in c.data.iter_mut().zip(&product) {
+                *x += alpha * y;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Borrowed view of the whole matrix, see `matrix_core::view`.
+    pub fn view(&self) -> MatrixView<'_, T> {
+        MatrixView::from_row_major(&self.data, self.rows, self.cols)
+    }
+
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut::from_row_major(&mut self.data, self.rows, self.cols)
+    }
+
+    pub fn row(&self, i: usize) -> MatrixView<'_, T> {
+        self.view().row(i)
+    }
+
+    pub fn col(&self, j: usize) -> MatrixView<'_, T> {
+        self.view().col(j)
+    }
+
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, T> {
+        self.view().submatrix(rows, cols)
+    }
+
+    pub fn submatrix_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, T> {
+        self.view_mut().into_submatrix(rows, cols)
+    }
+
+    /// `Aᵀ` as a view over the same data, without copying.
+    pub fn transposed(&self) -> MatrixView<'_, T> {
+        self.view().transpose()
+    }
+
+    /// Copies a view into a new matrix.
+    pub fn from_view(view: &MatrixView<T>) -> Matrix<T> {
+        Matrix {
+            rows: view.rows(),
+            cols: view.cols(),
+            data: view.to_vec(),
+        }
+    }
+
+    /// `add` for views, e.g. two blocks of the same matrix.
+    pub fn add_views(a: &MatrixView<T>, b: &MatrixView<T>) -> Matrix<T> {
+        Matrix::try_add_views(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add_views(a: &MatrixView<T>, b: &MatrixView<T>) -> Result<Matrix<T>, MatrixError> {
+        let mut result = Matrix::zeros(a.rows(), a.cols());
+        view::add(a, b, &mut result.view_mut())?;
+        Ok(result)
+    }
+
+    /// `multiply` for views: blocks, rows, columns or transposed views are
+    /// read in place.
+    pub fn multiply_views(a: &MatrixView<T>, b: &MatrixView<T>) -> Matrix<T> {
+        Matrix::try_multiply_views(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_multiply_views(
+        a: &MatrixView<T>,
+        b: &MatrixView<T>,
+    ) -> Result<Matrix<T>, MatrixError> {
+        if a.cols() != b.rows() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (a.rows(), a.cols()),
+                rhs: (b.rows(), b.cols()),
+            });
+        }
+
+        let mut result = Matrix::zeros(a.rows(), b.cols());
+        view::multiply_add(a, b, &mut result.view_mut())?;
+        Ok(result)
+    }
+
+    /// Non-zero entries as CSR, see `matrix_core::sparse`.
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix::from_dense(&self.data, self.rows, self.cols)
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix::from_dense(&self.data, self.rows, self.cols)
+    }
+
+    pub fn from_csr(sparse: &CsrMatrix<T>) -> Matrix<T> {
+        Matrix {
+            rows: sparse.rows(),
+            cols: sparse.cols(),
+            data: sparse.to_dense(),
+        }
+    }
+
+    pub fn from_csc(sparse: &CscMatrix<T>) -> Matrix<T> {
+        Matrix {
+            rows: sparse.rows(),
+            cols: sparse.cols(),
+            data: sparse.to_dense(),
+        }
+    }
+
+    /// `A * B` for a sparse `A`; only the non-zeros of A are visited.
+    pub fn sparse_multiply(a: &CsrMatrix<T>, b: &Matrix<T>) -> Matrix<T> {
+        Matrix::try_sparse_multiply(a, b).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_sparse_multiply(a: &CsrMatrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        Ok(Matrix {
+            rows: a.rows(),
+            cols: b.cols,
+            data: a.multiply_dense(&b.view())?,
+        })
+    }
+}
+
+/// `c += a * b` for row-major `n x m` and `m x p` slices, one
+/// `block_size x block_size` tile at a time. For types with a SIMD
+/// micro-kernel each tile goes through `gemm` and `loop_order` is unused.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn multiply_tiled<T: Scalar>(
+    a: &[T],
+    b: &[T],
+    c: &mut [T],
+    n: usize,
+    m: usize,
+    p: usize,
+    block_size: usize,
+    loop_order: LoopOrder,
+) {
+    for ii in (0..n).step_by(block_size) {
+        for jj in (0..p).step_by(block_size) {
+            for kk in (0..m).step_by(block_size) {
+                // para cada bloco
+                let i_max = (ii + block_size).min(n);
+                let j_max = (jj + block_size).min(p);
+                let k_max = (kk + block_size).min(m);
+
+                if T::has_simd_kernel() {
+                    gemm::gemm(
+                        i_max - ii,
+                        j_max - jj,
+                        k_max - kk,
+                        &a[ii * m + kk..],
+                        m,
+                        &b[kk * p + jj..],
+                        p,
+                        &mut c[ii * p + jj..],
+                        p,
+                    );
+                    continue;
+                }
+
+                match loop_order {
+                    LoopOrder::Ijk => {
+                        for i in ii..i_max {
+                            for j in jj..j_max {
+                                let mut sum = c[i * p + j];
+
+                                for k in kk..k_max {
+                                    sum += a[i * m + k] * b[k * p + j];
+                                }
+
+                                c[i * p + j] = sum;
+                            }
+                        }
+                    }
+                    LoopOrder::Ikj => {
+                        for i in ii..i_max {
+                            for k in kk..k_max {
+                                let a_ik = a[i * m + k];
+
+                                for j in jj..j_max {
+                                    c[i * p + j] += a_ik * b[k * p + j];
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Matrix<Complex<T>> {
+    pub fn conjugate(&self) -> Matrix<Complex<T>> {
+        self.apply_function(&|z| z.conj())
+    }
+
+    /// Hermitian adjoint `Aᴴ`: the transpose with every element conjugated.
+    pub fn conjugate_transpose(&self) -> Matrix<Complex<T>> {
+        let mut buffer = vec![Complex::zero(); self.cols * self.rows];
+
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                buffer[j * self.rows + i] = self.data[i * self.cols + j].conj();
+            }
+        }
+
+        Matrix {
+            rows: self.cols,
+            cols: self.rows,
+            data: buffer,
+        }
+    }
+}
+
+impl<T: Scalar> MatrixOps for Matrix<T> {
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.data[i * self.cols + j]
+    }
+
+    fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::zeros(rows, cols)
+    }
+
+    fn random(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::random(rows, cols)
+    }
+
+    fn identity(size: usize) -> Matrix<T> {
+        Matrix::identity(size)
+    }
+
+    fn add(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.add(other, default_tile_sizes::<T>().add)
+    }
+
+    fn try_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_add(other, default_tile_sizes::<T>().add)
+    }
+
+    fn subtract(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.subtract(other, default_tile_sizes::<T>().subtract)
+    }
+
+    fn try_subtract(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_subtract(other, default_tile_sizes::<T>().subtract)
+    }
+
+    fn multiply(&self, other: &Matrix<T>) -> Matrix<T> {
+        let tuning = tuning::<T>();
+        self.multiply_with(other, tuning.block_size, tuning.loop_order)
+    }
+
+    fn try_multiply(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let tuning = tuning::<T>();
+        self.try_multiply_with(other, tuning.block_size, tuning.loop_order)
+    }
+
+    fn hadamard_product(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.hadamard_product(other)
+    }
+
+    fn try_hadamard_product(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_hadamard_product(other)
+    }
+
+    fn transpose(&self) -> Matrix<T> {
+        self.transpose()
+    }
+
+    fn apply_function(&self, func: &dyn Fn(T) -> T) -> Matrix<T> {
+        self.apply_function(func)
     }
 }
 
-impl fmt::Display for Matrix {
+impl<T: Scalar> fmt::Display for Matrix<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let max_width = self
             .data
@@ -246,9 +954,9 @@ impl fmt::Display for Matrix {
     }
 }
 
-impl From<Vec<f64>> for Matrix {
+impl<T: Scalar> From<Vec<T>> for Matrix<T> {
     /// This method will always return a matrix with rows = vec.len() and cols = 1
-    fn from(vec: Vec<f64>) -> Self {
+    fn from(vec: Vec<T>) -> Self {
         let rows = vec.len();
         let cols = 1;
         Matrix {
@@ -1,14 +1,87 @@
 use std::env;
+use std::str::FromStr;
 
-use contiguous_tiled::Matrix;
+use contiguous_tiled::{tuning, BinaryElement, LoopOrder, MappedMatrix, Matrix};
 
 fn main() {
     let size = env::var("SIZE").unwrap_or_else(|_| "1000".to_string());
-    let block_size = env::var("BLOCK_SIZE").unwrap_or_else(|_| "512".to_string());
+    let kernel = env::var("KERNEL").unwrap_or_else(|_| "tiled".to_string());
+    let op = env::var("OP").unwrap_or_else(|_| "multiply".to_string());
+    let cutoff = env::var("CUTOFF").unwrap_or_else(|_| "64".to_string());
+    let dtype = env::var("DTYPE").unwrap_or_else(|_| "f64".to_string());
     let size = size.parse::<usize>().unwrap();
-    let block_size = block_size.parse::<usize>().unwrap();
+    // sem BLOCK_SIZE/LOOP_ORDER vale o que o autotune salvou, ou o bloco
+    // calculado pelo tamanho do cache
+    let block_size = env::var("BLOCK_SIZE")
+        .ok()
+        .map(|block_size| block_size.parse::<usize>().unwrap());
+    let loop_order = env::var("LOOP_ORDER")
+        .ok()
+        .map(|loop_order| loop_order.parse::<LoopOrder>().unwrap());
+    let cutoff = cutoff.parse::<usize>().unwrap();
 
-    let matrix_a = Matrix::random(size, size);
-    let matrix_b = Matrix::random(size, size);
-    matrix_a.multiply(&matrix_b, block_size);
+    match dtype.as_str() {
+        "f32" => run::<f32>(size, block_size, loop_order, cutoff, &kernel, &op),
+        "f64" => run::<f64>(size, block_size, loop_order, cutoff, &kernel, &op),
+        other => panic!("DTYPE desconhecido: {} (use f32 ou f64)", other),
+    }
+}
+
+fn run<T: BinaryElement + FromStr>(
+    size: usize,
+    block_size: Option<usize>,
+    loop_order: Option<LoopOrder>,
+    cutoff: usize,
+    kernel: &str,
+    op: &str,
+) {
+    let tuning = tuning::<T>();
+    let block_size = block_size.unwrap_or(tuning.block_size);
+    let loop_order = loop_order.unwrap_or(tuning.loop_order);
+    let matrix_a = input::<T>("INPUT_A", size, size);
+
+    if op == "transpose" {
+        match kernel {
+            "recursive" => {
+                matrix_a.transpose_recursive();
+            }
+            "in_place" => {
+                let mut matrix_a = matrix_a;
+                matrix_a.transpose_in_place();
+            }
+            _ => {
+                matrix_a.transpose();
+            }
+        }
+        return;
+    }
+
+    let matrix_b = input::<T>("INPUT_B", matrix_a.cols, matrix_a.cols);
+
+    match kernel {
+        "packed" => matrix_a.multiply_packed(&matrix_b),
+        "strassen" => matrix_a.multiply_strassen(&matrix_b, cutoff),
+        "recursive" => matrix_a.multiply_recursive(&matrix_b),
+        _ => matrix_a.multiply_with(&matrix_b, block_size, loop_order),
+    };
+}
+
+// INPUT_A/INPUT_B apontam para arquivos Matrix Market (.mtx), como os da
+// SuiteSparse, ou no formato binário de `matrix_core::io::binary` (qualquer
+// outra extensão, gerados com `generate_input`); sem elas as matrizes são
+// aleatórias. B aleatória tem o número de colunas de A nos dois lados.
+fn input<T: BinaryElement + FromStr>(var: &str, rows: usize, cols: usize) -> Matrix<T> {
+    let path = match env::var(var) {
+        Ok(path) => path,
+        Err(_) => return Matrix::random(rows, cols),
+    };
+    let matrix = if path.ends_with(".mtx") {
+        Matrix::read_matrix_market(&path)
+    } else {
+        // o arquivo é mapeado em vez de lido; a cópia para o `Vec` da
+        // matriz continua porque os kernels trabalham com `Matrix`, e é ela
+        // que deixa um arquivo por coluna row-major
+        MappedMatrix::open(&path).map(|mapped| Matrix::from_view(&mapped.view()))
+    };
+    matrix.unwrap_or_else(|e| panic!("{}: {}", path, e))
 }
@@ -0,0 +1,8 @@
+// Operadores do `std::ops` para `Matrix`, gerados pela macro
+// `matrix_core::impl_contiguous_operators`. Eles chamam os métodos do
+// `MatrixOps`, então o `+` usa o bloco de `default_tile_sizes` e o `*`, o
+// bloco e a ordem dos loops de `tuning`.
+
+use crate::Matrix;
+
+matrix_core::impl_contiguous_operators!(Matrix);
@@ -0,0 +1,79 @@
+// Versões cache-oblivious: em vez de um `block_size` fixo, o problema é
+// dividido ao meio pela maior dimensão até ficar pequeno. Em algum nível da
+// recursão os pedaços cabem no L1, em outro no L2, e assim por diante, sem
+// que o código precise saber o tamanho de nenhum cache. `LEAF` só evita o
+// custo das chamadas nos pedaços minúsculos, não é ajustado para um cache.
+
+use matrix_core::Scalar;
+
+const LEAF: usize = 32;
+
+/// `c[i0..i1][j0..j1] += a[i0..i1][k0..k1] * b[k0..k1][j0..j1]`, where `a` has
+/// `m` columns and `b` and `c` have `p` columns.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn multiply<T: Scalar>(
+    a: &[T],
+    b: &[T],
+    c: &mut [T],
+    m: usize,
+    p: usize,
+    (i0, i1): (usize, usize),
+    (k0, k1): (usize, usize),
+    (j0, j1): (usize, usize),
+) {
+    let rows = i1 - i0;
+    let inner = k1 - k0;
+    let cols = j1 - j0;
+
+    if rows <= LEAF && inner <= LEAF && cols <= LEAF {
+        for i in i0..i1 {
+            for k in k0..k1 {
+                let a_ik = a[i * m + k];
+
+                for j in j0..j1 {
+                    c[i * p + j] += a_ik * b[k * p + j];
+                }
+            }
+        }
+    } else if rows >= inner && rows >= cols {
+        let mid = i0 + rows / 2;
+        multiply(a, b, c, m, p, (i0, mid), (k0, k1), (j0, j1));
+        multiply(a, b, c, m, p, (mid, i1), (k0, k1), (j0, j1));
+    } else if cols >= inner {
+        let mid = j0 + cols / 2;
+        multiply(a, b, c, m, p, (i0, i1), (k0, k1), (j0, mid));
+        multiply(a, b, c, m, p, (i0, i1), (k0, k1), (mid, j1));
+    } else {
+        // as duas metades somam no mesmo pedaço de C
+        let mid = k0 + inner / 2;
+        multiply(a, b, c, m, p, (i0, i1), (k0, mid), (j0, j1));
+        multiply(a, b, c, m, p, (i0, i1), (mid, k1), (j0, j1));
+    }
+}
+
+/// `dst[j][i] = src[i][j]` for `i` in `i0..i1` and `j` in `j0..j1`, where
+/// `src` has `cols` columns and `dst` has `rows` columns.
+pub(crate) fn transpose<T: Scalar>(
+    src: &[T],
+    dst: &mut [T],
+    rows: usize,
+    cols: usize,
+    (i0, i1): (usize, usize),
+    (j0, j1): (usize, usize),
+) {
+    if i1 - i0 <= LEAF && j1 - j0 <= LEAF {
+        for i in i0..i1 {
+            for j in j0..j1 {
+                dst[j * rows + i] = src[i * cols + j];
+            }
+        }
+    } else if i1 - i0 >= j1 - j0 {
+        let mid = i0 + (i1 - i0) / 2;
+        transpose(src, dst, rows, cols, (i0, mid), (j0, j1));
+        transpose(src, dst, rows, cols, (mid, i1), (j0, j1));
+    } else {
+        let mid = j0 + (j1 - j0) / 2;
+        transpose(src, dst, rows, cols, (i0, i1), (j0, mid));
+        transpose(src, dst, rows, cols, (i0, i1), (mid, j1));
+    }
+}
@@ -0,0 +1,19 @@
+// Strassen-Winograd de `matrix_core::strassen`, com as folhas no kernel
+// tiled deste crate e `DEFAULT_BLOCK_SIZE`.
+
+use matrix_core::{strassen, Scalar};
+
+use crate::{multiply_tiled, LoopOrder, DEFAULT_BLOCK_SIZE};
+
+pub(crate) use matrix_core::strassen::{pad, padded_size, unpad};
+
+/// `a * b` for `s x s` matrices, where `s` comes from `padded_size`.
+pub(crate) fn multiply<T: Scalar>(a: &[T], b: &[T], s: usize, cutoff: usize) -> Vec<T> {
+    strassen::multiply(a, b, s, cutoff, &kernel)
+}
+
+fn kernel<T: Scalar>(a: &[T], b: &[T], s: usize) -> Vec<T> {
+    let mut c = vec![T::zero(); s * s];
+    multiply_tiled(a, b, &mut c, s, s, s, DEFAULT_BLOCK_SIZE, LoopOrder::Ijk);
+    c
+}
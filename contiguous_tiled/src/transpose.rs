@@ -0,0 +1,91 @@
+// Transposta em blocos: dentro de um bloco `block_size x block_size` a
+// leitura anda pela linha de `src` e a escrita salta `rows` elementos, mas as
+// poucas linhas de `dst` tocadas pelo bloco continuam no cache até ele
+// terminar. Sem blocos, cada escrita cai numa linha de cache diferente.
+//
+// A versão in-place de matrizes retangulares segue os ciclos da permutação
+// `k -> k * rows mod (rows * cols - 1)`, que leva a posição de `A[i][j]` para
+// a de `Aᵀ[j][i]`.
+
+use matrix_core::Scalar;
+
+/// `dst = srcᵀ` for a row-major `rows x cols` source.
+pub(crate) fn tiled<T: Scalar>(
+    src: &[T],
+    dst: &mut [T],
+    rows: usize,
+    cols: usize,
+    block_size: usize,
+) {
+    let block_size = block_size.max(1);
+
+    for ii in (0..rows).step_by(block_size) {
+        for jj in (0..cols).step_by(block_size) {
+            let i_max = (ii + block_size).min(rows);
+            let j_max = (jj + block_size).min(cols);
+
+            for i in ii..i_max {
+                for j in jj..j_max {
+                    dst[j * rows + i] = src[i * cols + j];
+                }
+            }
+        }
+    }
+}
+
+/// Transposes an `n x n` matrix in place, swapping each tile above the
+/// diagonal with its mirror below it.
+pub(crate) fn square_in_place<T: Scalar>(data: &mut [T], n: usize, block_size: usize) {
+    let block_size = block_size.max(1);
+
+    for ii in (0..n).step_by(block_size) {
+        let i_max = (ii + block_size).min(n);
+
+        for jj in (ii..n).step_by(block_size) {
+            let j_max = (jj + block_size).min(n);
+
+            for i in ii..i_max {
+                // no bloco da diagonal só o triângulo de cima
+                let j_start = if ii == jj { i + 1 } else { jj };
+
+                for j in j_start..j_max {
+                    data.swap(i * n + j, j * n + i);
+                }
+            }
+        }
+    }
+}
+
+/// Transposes a row-major `rows x cols` matrix in place by following the
+/// cycles of the permutation. Uses one bit per element to mark the visited
+/// positions.
+pub(crate) fn in_place<T: Scalar>(data: &mut [T], rows: usize, cols: usize) {
+    let len = rows * cols;
+    if len < 3 {
+        return;
+    }
+
+    // a primeira e a última posição nunca mudam
+    let modulus = len - 1;
+    let mut visited = vec![0u64; len.div_ceil(64)];
+
+    for start in 1..modulus {
+        if visited[start / 64] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+
+        let mut value = data[start];
+        let mut position = start;
+
+        loop {
+            let next = position * rows % modulus;
+            std::mem::swap(&mut value, &mut data[next]);
+            visited[next / 64] |= 1 << (next % 64);
+            position = next;
+
+            if position == start {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::env;
+use std::fs::File;
+use std::io::BufWriter;
+
+use matrix_core::io::binary::{self, BinaryElement, Layout};
+
+// Gera uma entrada fixa para os benchmarks (INPUT_A/INPUT_B) no formato
+// binário, para que todas as execuções multipliquem as mesmas matrizes.
+// ROWS e COLS valem SIZE quando ausentes.
+//
+//     SIZE=2000 OUTPUT=a.bin cargo run --release -p matrix_core --bin generate_input
+
+fn write<T: BinaryElement>(path: &str, rows: usize, cols: usize) {
+    let data: Vec<T> = (0..rows * cols).map(|_| T::random()).collect();
+    let file = File::create(path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    binary::write(BufWriter::new(file), Layout::RowMajor, rows, cols, &data)
+        .unwrap_or_else(|e| panic!("{}: {}", path, e));
+}
+
+fn main() {
+    let size = env::var("SIZE").unwrap_or_else(|_| "1000".to_string());
+    let rows = env::var("ROWS").unwrap_or_else(|_| size.clone());
+    let cols = env::var("COLS").unwrap_or_else(|_| size.clone());
+    let dtype = env::var("DTYPE").unwrap_or_else(|_| "f64".to_string());
+    let rows = rows.parse::<usize>().unwrap();
+    let cols = cols.parse::<usize>().unwrap();
+    let output =
+        env::var("OUTPUT").unwrap_or_else(|_| format!("input_{}x{}_{}.bin", rows, cols, dtype));
+
+    match dtype.as_str() {
+        "f32" => write::<f32>(&output, rows, cols),
+        "f64" => write::<f64>(&output, rows, cols),
+        other => panic!("DTYPE desconhecido: {} (use f32 ou f64)", other),
+    }
+    println!("{}", output);
+}
@@ -0,0 +1,177 @@
+// Tamanhos de cache lidos do sysfs do Linux, usados para escolher o tamanho
+// dos blocos quando nada foi ajustado pelo autotune. Cada `indexN` em
+// `/sys/devices/system/cpu/cpuX/cache` descreve um cache:
+//
+//     level = 2
+//     type = Unified
+//     size = 2048K
+//     coherency_line_size = 64
+//
+// Fora do Linux (ou num container sem sysfs) valem as constantes de
+// `FALLBACK`.
+
+use std::fs;
+use std::mem::size_of;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const SYSFS_CPU: &str = "/sys/devices/system/cpu";
+
+/// Sizes used when sysfs can't be read, typical of a desktop x86 core.
+pub const FALLBACK: CacheTopology = CacheTopology {
+    l1: 32 * 1024,
+    l2: 256 * 1024,
+    l3: 8 * 1024 * 1024,
+    line_size: 64,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheLevel {
+    L1,
+    L2,
+    L3,
+}
+
+/// Data cache sizes of one core, in bytes. A machine without L3 reports its
+/// L2 size as `l3`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheTopology {
+    pub l1: usize,
+    pub l2: usize,
+    pub l3: usize,
+    pub line_size: usize,
+}
+
+/// Block sizes, in elements per side, for the tiled kernels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileSizes {
+    /// Three tiles (A, B and C) in L2, where the tiles of B are reused.
+    pub multiply: usize,
+    /// Three tiles (both operands and the result) in L1.
+    pub add: usize,
+    pub subtract: usize,
+    /// Source and destination tiles in L1, the same size as `add`.
+    pub transpose: usize,
+}
+
+/// Topology detected from sysfs, or `FALLBACK`. Read once per process.
+pub fn topology() -> CacheTopology {
+    static TOPOLOGY: OnceLock<CacheTopology> = OnceLock::new();
+
+    *TOPOLOGY.get_or_init(|| CacheTopology::detect().unwrap_or(FALLBACK))
+}
+
+impl CacheTopology {
+    pub fn detect() -> Option<CacheTopology> {
+        CacheTopology::from_sysfs(Path::new(SYSFS_CPU))
+    }
+
+    /// Reads the caches of the first `cpuN` directory under `root` that has
+    /// both L1 data and L2. Instruction caches are ignored.
+    pub fn from_sysfs(root: &Path) -> Option<CacheTopology> {
+        let mut cpus: Vec<(usize, _)> = fs::read_dir(root)
+            .ok()?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name();
+                let id = name.to_str()?.strip_prefix("cpu")?.parse().ok()?;
+                Some((id, entry.path()))
+            })
+            .collect();
+        cpus.sort();
+
+        cpus.iter()
+            .find_map(|(_, cpu)| CacheTopology::from_cpu(&cpu.join("cache")))
+    }
+
+    fn from_cpu(cache: &Path) -> Option<CacheTopology> {
+        let mut sizes = [0usize; 3];
+        let mut line_size = 0;
+
+        for entry in fs::read_dir(cache).ok()?.flatten() {
+            let index = entry.path();
+            let read = |file: &str| fs::read_to_string(index.join(file)).ok();
+
+            if read("type").is_some_and(|kind| kind.trim() == "Instruction") {
+                continue;
+            }
+            let Some(level) = read("level").and_then(|level| level.trim().parse::<usize>().ok())
+            else {
+                continue;
+            };
+            let Some(size) = read("size").and_then(|size| parse_size(&size)) else {
+                continue;
+            };
+
+            if (1..=3).contains(&level) {
+                sizes[level - 1] = sizes[level - 1].max(size);
+            }
+            if let Some(line) = read("coherency_line_size").and_then(|l| l.trim().parse().ok()) {
+                line_size = line_size.max(line);
+            }
+        }
+
+        let [l1, l2, l3] = sizes;
+        if l1 == 0 || l2 == 0 {
+            return None;
+        }
+
+        Some(CacheTopology {
+            l1,
+            l2,
+            l3: if l3 == 0 { l2 } else { l3 },
+            line_size: if line_size == 0 {
+                FALLBACK.line_size
+            } else {
+                line_size
+            },
+        })
+    }
+
+    pub fn size(&self, level: CacheLevel) -> usize {
+        match level {
+            CacheLevel::L1 => self.l1,
+            CacheLevel::L2 => self.l2,
+            CacheLevel::L3 => self.l3,
+        }
+    }
+
+    /// Largest `b` with three `b x b` tiles of `element_size` bytes in the
+    /// cache, rounded down to whole cache lines and never below one line.
+    pub fn tile_size(&self, level: CacheLevel, element_size: usize) -> usize {
+        let element_size = element_size.max(1);
+        let per_line = (self.line_size / element_size).max(1);
+        let side = (self.size(level) / (3 * element_size)).isqrt();
+
+        (side / per_line * per_line).max(per_line)
+    }
+
+    pub fn tile_sizes<T>(&self) -> TileSizes {
+        let elementwise = self.tile_size(CacheLevel::L1, size_of::<T>());
+
+        TileSizes {
+            multiply: self.tile_size(CacheLevel::L2, size_of::<T>()),
+            add: elementwise,
+            subtract: elementwise,
+            transpose: elementwise,
+        }
+    }
+}
+
+/// Parses the sysfs `size` format: `48K`, `2048K`, `300M` or plain bytes.
+fn parse_size(size: &str) -> Option<usize> {
+    let size = size.trim();
+    let (digits, unit) = match size.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((i, _)) => size.split_at(i),
+        None => (size, ""),
+    };
+    let value: usize = digits.parse().ok()?;
+
+    match unit.trim() {
+        "" => Some(value),
+        "K" | "KB" | "KiB" => Some(value * 1024),
+        "M" | "MB" | "MiB" => Some(value * 1024 * 1024),
+        "G" | "GB" | "GiB" => Some(value * 1024 * 1024 * 1024),
+        _ => None,
+    }
+}
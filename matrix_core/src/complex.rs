@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use crate::Scalar;
+
+/// Complex number `re + im·i`, usable as a `Matrix` element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    pub fn new(re: T, im: T) -> Complex<T> {
+        Complex { re, im }
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Complex<T> {
+    pub fn conj(self) -> Complex<T> {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// `|z|²`, real because `z * conj(z)` has no imaginary part.
+    pub fn norm_sqr(self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl<T: Scalar> Add for Complex<T> {
+    type Output = Complex<T>;
+
+    fn add(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl<T: Scalar> Sub for Complex<T> {
+    type Output = Complex<T>;
+
+    fn sub(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl<T: Scalar> Mul for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Neg for Complex<T> {
+    type Output = Complex<T>;
+
+    fn neg(self) -> Complex<T> {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<T: Scalar> AddAssign for Complex<T> {
+    fn add_assign(&mut self, other: Complex<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Scalar> fmt::Display for Complex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `pad` mantém o alinhamento pedido pelo `Display` do `Matrix`
+        f.pad(&format!("{}{:+}i", self.re, self.im))
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Scalar for Complex<T> {
+    fn zero() -> Complex<T> {
+        Complex::new(T::zero(), T::zero())
+    }
+
+    fn one() -> Complex<T> {
+        Complex::new(T::one(), T::zero())
+    }
+
+    fn random() -> Complex<T> {
+        Complex::new(T::random(), T::random())
+    }
+}
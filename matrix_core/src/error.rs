@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons a matrix operation can be rejected, returned by the `try_*` methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The shapes of the operands do not fit the operation named by `op`,
+    /// e.g. adding a 2x2 to a 2x1, or multiplying when `lhs.cols != rhs.rows`.
+    DimensionMismatch {
+        op: &'static str,
+        lhs: (usize, usize),
+        rhs: (usize, usize),
+    },
+    /// The data handed to a constructor does not have the declared size.
+    InvalidDataLength { expected: usize, actual: usize },
+    /// The operation is only defined for square matrices.
+    NotSquare {
+        op: &'static str,
+        rows: usize,
+        cols: usize,
+    },
+    /// The matrix has no inverse, so the system cannot be solved.
+    Singular,
+    /// Cholesky found a non-positive pivot at the given column, so the
+    /// matrix is not symmetric positive definite.
+    NotPositiveDefinite { pivot: usize },
+    /// Least squares needs at least as many rows (equations) as columns.
+    Underdetermined { rows: usize, cols: usize },
+    /// The symmetric eigensolver got a matrix with `a[i][j] != a[j][i]`.
+    NotSymmetric,
+    /// An iterative method hit its cap of `iterations` sweeps or steps
+    /// before converging, e.g. on a matrix with NaNs.
+    NotConverged { iterations: usize },
+    /// The offsets or indices of a sparse matrix are inconsistent.
+    InvalidSparseStructure { reason: &'static str },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { op, lhs, rhs } => write!(
+                f,
+                "Dimension mismatch in {}. {}x{} & {}x{}",
+                op, lhs.0, lhs.1, rhs.0, rhs.1
+            ),
+            MatrixError::InvalidDataLength { expected, actual } => {
+                write!(f, "Invalid Size. expected {} & got {}", expected, actual)
+            }
+            MatrixError::NotSquare { op, rows, cols } => {
+                write!(f, "Cannot {} a non-square matrix. {}x{}", op, rows, cols)
+            }
+            MatrixError::Singular => write!(f, "Matrix is singular"),
+            MatrixError::NotPositiveDefinite { pivot } => {
+                write!(f, "Matrix is not positive definite. pivot {}", pivot)
+            }
+            MatrixError::Underdetermined { rows, cols } => {
+                write!(
+                    f,
+                    "Cannot solve an underdetermined system. {}x{}",
+                    rows, cols
+                )
+            }
+            MatrixError::NotSymmetric => write!(f, "Matrix is not symmetric"),
+            MatrixError::NotConverged { iterations } => {
+                write!(f, "Did not converge. {} iterations", iterations)
+            }
+            MatrixError::InvalidSparseStructure { reason } => {
+                write!(f, "Invalid sparse structure. {}", reason)
+            }
+        }
+    }
+}
+
+impl Error for MatrixError {}
@@ -0,0 +1,189 @@
+// GEMM no estilo BLIS/GotoBLAS: os painéis de A e B são copiados para
+// buffers contíguos na ordem em que o micro-kernel os lê, e o micro-kernel
+// acumula um bloco MR x NR de C em registradores.
+
+use crate::simd::{self, SimdLevel};
+use crate::Scalar;
+
+/// Linhas de C calculadas por chamada do micro-kernel.
+pub const MR: usize = 4;
+/// Colunas de C calculadas por chamada do micro-kernel.
+pub const NR: usize = 8;
+
+// Painel de A (MC x KC) deve caber no L2, painel de B (KC x NC) no L3
+const MC: usize = 128;
+const KC: usize = 256;
+const NC: usize = 4096;
+
+/// `C += A * B` for row-major slices, where `A` is `m x k`, `B` is `k x n` and
+/// `C` is `m x n`. `lda`, `ldb` and `ldc` are the row strides of each slice.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm<T: Scalar>(
+    m: usize,
+    n: usize,
+    k: usize,
+    a: &[T],
+    lda: usize,
+    b: &[T],
+    ldb: usize,
+    c: &mut [T],
+    ldc: usize,
+) {
+    packed(T::micro_kernel(), m, n, k, a, lda, b, ldb, c, ldc);
+}
+
+/// `gemm` with the micro-kernel forced to a given instruction set. Levels
+/// the CPU lacks fall back to `simd::level()`.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm_with(
+    level: SimdLevel,
+    m: usize,
+    n: usize,
+    k: usize,
+    a: &[f64],
+    lda: usize,
+    b: &[f64],
+    ldb: usize,
+    c: &mut [f64],
+    ldc: usize,
+) {
+    packed(
+        simd::micro_kernel_for(level),
+        m,
+        n,
+        k,
+        a,
+        lda,
+        b,
+        ldb,
+        c,
+        ldc,
+    );
+}
+
+/// Signature of the micro-kernel: `kc`, packed A panel, packed B panel, the
+/// `mr x nr` corner of C and its row stride.
+pub type MicroKernel<T> = fn(usize, &[T], &[T], &mut [T], usize, usize, usize);
+
+#[allow(clippy::too_many_arguments)]
+fn packed<T: Scalar>(
+    micro_kernel: MicroKernel<T>,
+    m: usize,
+    n: usize,
+    k: usize,
+    a: &[T],
+    lda: usize,
+    b: &[T],
+    ldb: usize,
+    c: &mut [T],
+    ldc: usize,
+) {
+    // do tamanho do problema, não dos limites, para que blocos pequenos (os
+    // tiles de `multiply`) não zerem painéis inteiros a cada chamada
+    let kc_max = KC.min(k);
+    let mut a_pack = vec![T::zero(); MC.min(m.next_multiple_of(MR)) * kc_max];
+    let mut b_pack = vec![T::zero(); kc_max * NC.min(n.next_multiple_of(NR))];
+
+    for jc in (0..n).step_by(NC) {
+        let nc = NC.min(n - jc);
+
+        for pc in (0..k).step_by(KC) {
+            let kc = KC.min(k - pc);
+            pack_b(kc, nc, &b[pc * ldb + jc..], ldb, &mut b_pack);
+
+            for ic in (0..m).step_by(MC) {
+                let mc = MC.min(m - ic);
+                pack_a(mc, kc, &a[ic * lda + pc..], lda, &mut a_pack);
+
+                for jr in (0..nc).step_by(NR) {
+                    let nr = NR.min(nc - jr);
+
+                    for ir in (0..mc).step_by(MR) {
+                        let mr = MR.min(mc - ir);
+
+                        micro_kernel(
+                            kc,
+                            &a_pack[ir * kc..(ir + MR) * kc],
+                            &b_pack[jr * kc..(jr + NR) * kc],
+                            &mut c[(ic + ir) * ldc + jc + jr..],
+                            ldc,
+                            mr,
+                            nr,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Copia um bloco `mc x kc` de A em painéis de MR linhas, cada painel
+/// guardado coluna a coluna. Linhas que faltam no último painel viram zero.
+fn pack_a<T: Scalar>(mc: usize, kc: usize, a: &[T], lda: usize, a_pack: &mut [T]) {
+    for ir in (0..mc).step_by(MR) {
+        let panel = &mut a_pack[ir * kc..(ir + MR) * kc];
+
+        for p in 0..kc {
+            for i in 0..MR {
+                panel[p * MR + i] = if ir + i < mc {
+                    a[(ir + i) * lda + p]
+                } else {
+                    T::zero()
+                };
+            }
+        }
+    }
+}
+
+/// Copia um bloco `kc x nc` de B em painéis de NR colunas, cada painel
+/// guardado linha a linha. Colunas que faltam no último painel viram zero.
+fn pack_b<T: Scalar>(kc: usize, nc: usize, b: &[T], ldb: usize, b_pack: &mut [T]) {
+    for jr in (0..nc).step_by(NR) {
+        let panel = &mut b_pack[jr * kc..(jr + NR) * kc];
+
+        for p in 0..kc {
+            for j in 0..NR {
+                panel[p * NR + j] = if jr + j < nc {
+                    b[p * ldb + jr + j]
+                } else {
+                    T::zero()
+                };
+            }
+        }
+    }
+}
+
+/// Acumula `a_panel * b_panel` (MR x kc vezes kc x NR) num bloco de
+/// registradores e soma as `mr x nr` posições válidas em C.
+pub(crate) fn micro_kernel_scalar<T: Scalar>(
+    kc: usize,
+    a_panel: &[T],
+    b_panel: &[T],
+    c: &mut [T],
+    ldc: usize,
+    mr: usize,
+    nr: usize,
+) {
+    let mut acc = [[T::zero(); NR]; MR];
+
+    for p in 0..kc {
+        let a = &a_panel[p * MR..p * MR + MR];
+        let b = &b_panel[p * NR..p * NR + NR];
+
+        for i in 0..MR {
+            for j in 0..NR {
+                acc[i][j] += a[i] * b[j];
+            }
+        }
+    }
+
+    store(&acc, c, ldc, mr, nr);
+}
+
+pub(crate) fn store<T: Scalar>(acc: &[[T; NR]; MR], c: &mut [T], ldc: usize, mr: usize, nr: usize) {
+    for i in 0..mr {
+        for j in 0..nr {
+            c[i * ldc + j] += acc[i][j];
+        }
+    }
+}
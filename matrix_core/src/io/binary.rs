@@ -0,0 +1,400 @@
+// Formato binário próprio, para carregar matrizes grandes sem parse: um
+// cabeçalho de 64 bytes e os elementos little-endian, do jeito que estão na
+// memória.
+//
+//     posição  bytes  campo
+//     0        8      "MATRIXB\0"
+//     8        2      versão (1)
+//     10       1      dtype (`Dtype`)
+//     11       1      layout (0 por linha, 1 por coluna)
+//     12       4      CRC-32 dos dados
+//     16       8      rows
+//     24       8      cols
+//     32       8      posição dos dados (64 na versão 1)
+//     40       24     zeros
+//
+// Com os dados começando em 64, um arquivo mapeado na memória (que começa
+// no início de uma página) já tem os elementos alinhados, e `MappedMatrix`
+// entrega uma `MatrixView` sobre eles sem copiar nada.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of_val};
+use std::path::Path;
+use std::slice;
+
+use memmap2::Mmap;
+
+use super::{read_bytes, FormatError};
+use crate::{MatrixError, MatrixView, Scalar};
+
+pub const MAGIC: [u8; 8] = *b"MATRIXB\0";
+pub const VERSION: u16 = 1;
+pub const HEADER_LEN: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dtype {
+    F32,
+    F64,
+    I32,
+    I64,
+    U32,
+    U64,
+}
+
+impl Dtype {
+    const ALL: [Dtype; 6] = [
+        Dtype::F32,
+        Dtype::F64,
+        Dtype::I32,
+        Dtype::I64,
+        Dtype::U32,
+        Dtype::U64,
+    ];
+
+    fn code(self) -> u8 {
+        self as u8 + 1
+    }
+
+    fn from_code(code: u8) -> Option<Dtype> {
+        Dtype::ALL.into_iter().find(|dtype| dtype.code() == code)
+    }
+
+    /// Bytes per element.
+    pub fn size(self) -> usize {
+        match self {
+            Dtype::F32 | Dtype::I32 | Dtype::U32 => 4,
+            Dtype::F64 | Dtype::I64 | Dtype::U64 => 8,
+        }
+    }
+}
+
+impl fmt::Display for Dtype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Dtype::F32 => "f32",
+            Dtype::F64 => "f64",
+            Dtype::I32 => "i32",
+            Dtype::I64 => "i64",
+            Dtype::U32 => "u32",
+            Dtype::U64 => "u64",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    RowMajor,
+    ColumnMajor,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BinaryHeader {
+    pub dtype: Dtype,
+    pub layout: Layout,
+    pub rows: usize,
+    pub cols: usize,
+    /// CRC-32 of the data bytes.
+    pub checksum: u32,
+}
+
+fn invalid(message: impl Into<String>) -> FormatError {
+    FormatError::InvalidHeader(message.into())
+}
+
+impl BinaryHeader {
+    fn to_bytes(self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[0..8].copy_from_slice(&MAGIC);
+        bytes[8..10].copy_from_slice(&VERSION.to_le_bytes());
+        bytes[10] = self.dtype.code();
+        bytes[11] = match self.layout {
+            Layout::RowMajor => 0,
+            Layout::ColumnMajor => 1,
+        };
+        bytes[12..16].copy_from_slice(&self.checksum.to_le_bytes());
+        bytes[16..24].copy_from_slice(&(self.rows as u64).to_le_bytes());
+        bytes[24..32].copy_from_slice(&(self.cols as u64).to_le_bytes());
+        bytes[32..40].copy_from_slice(&(HEADER_LEN as u64).to_le_bytes());
+        bytes
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<BinaryHeader, FormatError> {
+        let bytes = bytes
+            .get(..HEADER_LEN)
+            .ok_or_else(|| invalid("file shorter than the header"))?;
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+
+        if bytes[0..8] != MAGIC {
+            return Err(invalid("not a matrix binary file"));
+        }
+        let version = u16::from_le_bytes([bytes[8], bytes[9]]);
+        if version != VERSION {
+            return Err(invalid(format!("unsupported version {}", version)));
+        }
+        let dtype = Dtype::from_code(bytes[10])
+            .ok_or_else(|| invalid(format!("unknown dtype code {}", bytes[10])))?;
+        let layout = match bytes[11] {
+            0 => Layout::RowMajor,
+            1 => Layout::ColumnMajor,
+            code => return Err(invalid(format!("unknown layout code {}", code))),
+        };
+        if u64_at(32) != HEADER_LEN as u64 {
+            return Err(invalid(format!(
+                "data must start at byte {} in version {}",
+                HEADER_LEN, VERSION
+            )));
+        }
+
+        let header = BinaryHeader {
+            dtype,
+            layout,
+            checksum: u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            rows: usize::try_from(u64_at(16)).map_err(|_| invalid("too many rows"))?,
+            cols: usize::try_from(u64_at(24)).map_err(|_| invalid("too many columns"))?,
+        };
+        header.data_len()?;
+        Ok(header)
+    }
+
+    /// Bytes of data after the header.
+    pub fn data_len(&self) -> Result<usize, FormatError> {
+        self.rows
+            .checked_mul(self.cols)
+            .and_then(|len| len.checked_mul(self.dtype.size()))
+            .ok_or_else(|| invalid("matrix too large for this platform"))
+    }
+}
+
+/// Element types stored as raw bytes.
+///
+/// # Safety
+///
+/// Implementors must be plain data with no padding, valid for any bit
+/// pattern, and `DTYPE.size()` bytes long, since the data of a file is read
+/// straight into them.
+pub unsafe trait BinaryElement: Scalar {
+    const DTYPE: Dtype;
+}
+
+unsafe impl BinaryElement for f32 {
+    const DTYPE: Dtype = Dtype::F32;
+}
+
+unsafe impl BinaryElement for f64 {
+    const DTYPE: Dtype = Dtype::F64;
+}
+
+unsafe impl BinaryElement for i32 {
+    const DTYPE: Dtype = Dtype::I32;
+}
+
+unsafe impl BinaryElement for i64 {
+    const DTYPE: Dtype = Dtype::I64;
+}
+
+unsafe impl BinaryElement for u32 {
+    const DTYPE: Dtype = Dtype::U32;
+}
+
+unsafe impl BinaryElement for u64 {
+    const DTYPE: Dtype = Dtype::U64;
+}
+
+fn as_bytes<T: BinaryElement>(data: &[T]) -> &[u8] {
+    // SAFETY: `BinaryElement` tipos não têm padding, então todo byte é
+    // inicializado
+    unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, size_of_val(data)) }
+}
+
+fn as_bytes_mut<T: BinaryElement>(data: &mut [T]) -> &mut [u8] {
+    // SAFETY: qualquer sequência de bytes é um valor válido de `T`
+    unsafe { slice::from_raw_parts_mut(data.as_mut_ptr() as *mut u8, size_of_val(data)) }
+}
+
+/// The file stores little-endian data as it is in memory.
+fn check_endian() -> Result<(), FormatError> {
+    if cfg!(target_endian = "big") {
+        return Err(FormatError::Unsupported("big-endian hosts"));
+    }
+    Ok(())
+}
+
+fn check_dtype<T: BinaryElement>(header: &BinaryHeader) -> Result<(), FormatError> {
+    if header.dtype != T::DTYPE {
+        return Err(invalid(format!(
+            "file has {} elements, expected {}",
+            header.dtype,
+            T::DTYPE
+        )));
+    }
+    Ok(())
+}
+
+fn check_checksum(header: &BinaryHeader, data: &[u8]) -> Result<(), FormatError> {
+    let actual = crc32fast::hash(data);
+    if actual != header.checksum {
+        return Err(FormatError::ChecksumMismatch {
+            expected: header.checksum,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Writes a `rows x cols` matrix stored in `layout` order.
+pub fn write<T, W>(
+    mut writer: W,
+    layout: Layout,
+    rows: usize,
+    cols: usize,
+    data: &[T],
+) -> Result<(), FormatError>
+where
+    T: BinaryElement,
+    W: Write,
+{
+    check_endian()?;
+    if data.len() != rows * cols {
+        return Err(FormatError::Matrix(MatrixError::InvalidDataLength {
+            expected: rows * cols,
+            actual: data.len(),
+        }));
+    }
+
+    let bytes = as_bytes(data);
+    let header = BinaryHeader {
+        dtype: T::DTYPE,
+        layout,
+        rows,
+        cols,
+        checksum: crc32fast::hash(bytes),
+    };
+    writer.write_all(&header.to_bytes())?;
+    writer.write_all(bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a file into `(rows, cols, data)`, with `data` row-major whatever
+/// the layout of the file. The checksum is verified.
+pub fn read<T, R>(mut reader: R) -> Result<(usize, usize, Vec<T>), FormatError>
+where
+    T: BinaryElement,
+    R: Read,
+{
+    check_endian()?;
+
+    let mut bytes = [0u8; HEADER_LEN];
+    reader.read_exact(&mut bytes)?;
+    let header = BinaryHeader::parse(&bytes)?;
+    check_dtype::<T>(&header)?;
+
+    // os bytes chegam antes de alocar a matriz, então um cabeçalho com um
+    // tamanho enorme num arquivo curto vira erro em vez de alocação
+    let bytes = read_bytes(&mut reader, header.data_len()?)?;
+    check_checksum(&header, &bytes)?;
+
+    let (rows, cols) = (header.rows, header.cols);
+    let mut data = vec![T::zero(); rows * cols];
+    as_bytes_mut(&mut data).copy_from_slice(&bytes);
+    drop(bytes);
+
+    if header.layout == Layout::RowMajor {
+        return Ok((rows, cols, data));
+    }
+    let view = MatrixView::new(&data, rows, cols, 1, rows)?;
+    Ok((rows, cols, view.to_vec()))
+}
+
+/// A matrix file mapped into memory, read only. The data stays in the page
+/// cache and `view` borrows it directly.
+pub struct MappedMatrix<T> {
+    map: Mmap,
+    header: BinaryHeader,
+    element: PhantomData<T>,
+}
+
+impl<T: BinaryElement> MappedMatrix<T> {
+    /// Maps the file and verifies the checksum, which reads every page
+    /// once.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<MappedMatrix<T>, FormatError> {
+        let mapped = MappedMatrix::open_unverified(path)?;
+        mapped.verify()?;
+        Ok(mapped)
+    }
+
+    /// Maps the file checking only the header, for inputs too large to
+    /// read up front.
+    pub fn open_unverified<P: AsRef<Path>>(path: P) -> Result<MappedMatrix<T>, FormatError> {
+        check_endian()?;
+
+        let file = File::open(path)?;
+        // SAFETY: o mapa é só de leitura; como em qualquer mmap, o arquivo
+        // não pode ser truncado ou alterado por outro processo enquanto
+        // estiver mapeado
+        let map = unsafe { Mmap::map(&file)? };
+
+        let header = BinaryHeader::parse(&map)?;
+        check_dtype::<T>(&header)?;
+        let data_len = header.data_len()?;
+        if map.len() - HEADER_LEN < data_len {
+            return Err(invalid(format!(
+                "expected {} bytes of data, found {}",
+                data_len,
+                map.len() - HEADER_LEN
+            )));
+        }
+        // o mapa começa numa página, então isso só falha em plataformas
+        // estranhas
+        if map[HEADER_LEN..].as_ptr().align_offset(align_of::<T>()) != 0 {
+            return Err(FormatError::Unsupported("a misaligned memory map"));
+        }
+
+        Ok(MappedMatrix {
+            map,
+            header,
+            element: PhantomData,
+        })
+    }
+
+    pub fn verify(&self) -> Result<(), FormatError> {
+        check_checksum(&self.header, as_bytes(self.as_slice()))
+    }
+
+    pub fn header(&self) -> BinaryHeader {
+        self.header
+    }
+
+    pub fn rows(&self) -> usize {
+        self.header.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.header.cols
+    }
+
+    /// The elements in the order of the file.
+    pub fn as_slice(&self) -> &[T] {
+        let len = self.header.rows * self.header.cols;
+        let data = &self.map[HEADER_LEN..];
+        // SAFETY: `open_unverified` conferiu o tamanho e o alinhamento, e
+        // qualquer sequência de bytes é um `T` válido
+        unsafe { slice::from_raw_parts(data.as_ptr() as *const T, len) }
+    }
+
+    /// The matrix without copying. A column-major file gives a view with
+    /// `col_stride == rows`.
+    pub fn view(&self) -> MatrixView<'_, T> {
+        let (rows, cols) = (self.header.rows, self.header.cols);
+        let (row_stride, col_stride) = match self.header.layout {
+            Layout::RowMajor => (cols, 1),
+            Layout::ColumnMajor => (1, rows),
+        };
+        MatrixView::new(self.as_slice(), rows, cols, row_stride, col_stride)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+}
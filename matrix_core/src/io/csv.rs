@@ -0,0 +1,220 @@
+// Matrizes em texto delimitado (CSV e variantes de planilha). Cada linha do
+// arquivo é uma linha da matriz:
+//
+//     1.5,2,3          (padrão)
+//     1.332,12;0,998   (`CsvFormat::decimal_comma()`, como os CSVs do perf
+//                       gerados pelo `analysis`)
+//
+// Células entre aspas são aceitas (`"1,5"` num arquivo separado por
+// vírgula), com `""` para uma aspa dentro delas. Linhas em branco são
+// ignoradas.
+
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{MatrixError, Scalar};
+
+/// How cells are separated and how numbers are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CsvFormat {
+    pub delimiter: char,
+    pub decimal_separator: char,
+    /// Digit grouping removed when reading, e.g. the `.` of `1.332,12`.
+    /// Never written.
+    pub thousands_separator: Option<char>,
+    /// The first line has column names. When writing, the names are the
+    /// column indices, as pandas writes an unnamed frame.
+    pub has_header: bool,
+}
+
+impl Default for CsvFormat {
+    fn default() -> CsvFormat {
+        CsvFormat {
+            delimiter: ',',
+            decimal_separator: '.',
+            thousands_separator: None,
+            has_header: false,
+        }
+    }
+}
+
+impl CsvFormat {
+    /// `;` between cells, decimal comma and `.` grouping, as spreadsheets in
+    /// pt-BR (and the perf CSVs of this repository) use.
+    pub fn decimal_comma() -> CsvFormat {
+        CsvFormat {
+            delimiter: ';',
+            decimal_separator: ',',
+            thousands_separator: Some('.'),
+            has_header: false,
+        }
+    }
+
+    fn check(&self) -> Result<(), FormatError> {
+        let decimal = self.decimal_separator;
+        if self.delimiter == decimal || self.thousands_separator == Some(decimal) {
+            return Err(FormatError::Unsupported(
+                "the decimal separator must differ from the delimiter and the grouping",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A file read by `read`: the column names, if the format has a header,
+/// and the values row-major.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvTable<T> {
+    pub header: Option<Vec<String>>,
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<T>,
+}
+
+/// Splits a line at the delimiter, removing the quotes of quoted cells.
+fn split_line(line: &str, delimiter: char) -> Result<Vec<String>, String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quote".to_string());
+    }
+    cells.push(cell);
+    Ok(cells)
+}
+
+/// Row and column are 1-based and count the header line, as a spreadsheet
+/// shows them.
+fn cell_error(row: usize, col: usize, message: impl Into<String>) -> FormatError {
+    FormatError::Cell {
+        row,
+        col,
+        message: message.into(),
+    }
+}
+
+fn parse_cell<T: FromStr>(cell: &str, format: &CsvFormat) -> Option<T> {
+    let mut number: String = cell
+        .trim()
+        .chars()
+        .filter(|&c| Some(c) != format.thousands_separator)
+        .collect();
+    if format.decimal_separator != '.' {
+        number = number.replace(format.decimal_separator, ".");
+    }
+    number.parse().ok()
+}
+
+/// Reads every non-blank line as a row. All rows must have the same number
+/// of cells.
+pub fn read<T, R>(reader: R, format: CsvFormat) -> Result<CsvTable<T>, FormatError>
+where
+    T: Scalar + FromStr,
+    R: BufRead,
+{
+    format.check()?;
+
+    let mut header = None;
+    let mut cols = None;
+    let mut rows = 0;
+    let mut data = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let row = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let cells = split_line(&line, format.delimiter).map_err(|m| cell_error(row, 1, m))?;
+        let expected = *cols.get_or_insert(cells.len());
+        if cells.len() != expected {
+            return Err(cell_error(
+                row,
+                expected.min(cells.len()) + 1,
+                format!("expected {} cells, found {}", expected, cells.len()),
+            ));
+        }
+
+        if format.has_header && header.is_none() {
+            header = Some(cells.iter().map(|cell| cell.trim().to_string()).collect());
+            continue;
+        }
+
+        for (col, cell) in cells.iter().enumerate() {
+            let value = parse_cell(cell, &format).ok_or_else(|| {
+                let message = if cell.trim().is_empty() {
+                    "empty cell".to_string()
+                } else {
+                    format!("invalid number '{}'", cell.trim())
+                };
+                cell_error(row, col + 1, message)
+            })?;
+            data.push(value);
+        }
+        rows += 1;
+    }
+
+    Ok(CsvTable {
+        header,
+        rows,
+        cols: cols.unwrap_or(0),
+        data,
+    })
+}
+
+/// Writes a row-major `rows x cols` matrix, one line per row.
+pub fn write<T, W>(
+    mut writer: W,
+    format: CsvFormat,
+    rows: usize,
+    cols: usize,
+    data: &[T],
+) -> Result<(), FormatError>
+where
+    T: Scalar,
+    W: Write,
+{
+    format.check()?;
+    if data.len() != rows * cols {
+        return Err(FormatError::Matrix(MatrixError::InvalidDataLength {
+            expected: rows * cols,
+            actual: data.len(),
+        }));
+    }
+
+    let delimiter = format.delimiter.to_string();
+    if format.has_header {
+        let names: Vec<String> = (0..cols).map(|j| j.to_string()).collect();
+        writeln!(writer, "{}", names.join(&delimiter))?;
+    }
+
+    let decimal = format.decimal_separator.to_string();
+    for row in data.chunks(cols.max(1)).take(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|value| value.to_string().replace('.', &decimal))
+            .collect();
+        writeln!(writer, "{}", cells.join(&delimiter))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
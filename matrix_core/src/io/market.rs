@@ -0,0 +1,519 @@
+// Formato Matrix Market (.mtx), o da SuiteSparse Matrix Collection:
+//
+//     %%MatrixMarket matrix coordinate real symmetric
+//     % comentários
+//     3 3 4
+//     1 1 2.0
+//     2 1 -1.0
+//     ...
+//
+// `coordinate` lista `linha coluna valor` com índices a partir de 1, e
+// `array` lista todos os valores por coluna. Em `symmetric` só o triângulo
+// de baixo é guardado (em `array`, coluna por coluna a partir da diagonal)
+// e o de cima é o espelho. Em `pattern` não há valores: toda entrada
+// listada vale 1.
+//
+// Campos `complex` e simetrias `skew-symmetric`/`hermitian` não são
+// suportados.
+
+use std::fmt::{self, LowerExp};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use super::FormatError;
+use crate::{CooMatrix, CsrMatrix, MatrixError, Scalar};
+
+const BANNER: &str = "%%MatrixMarket";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketFormat {
+    /// Only the listed entries, one per line.
+    Coordinate,
+    /// Every entry, column by column.
+    Array,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketField {
+    Real,
+    Integer,
+    /// No values; every listed entry is one. Coordinate format only.
+    Pattern,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarketSymmetry {
+    General,
+    /// Only the lower triangle is stored.
+    Symmetric,
+}
+
+/// The `%%MatrixMarket matrix ...` line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MarketHeader {
+    pub format: MarketFormat,
+    pub field: MarketField,
+    pub symmetry: MarketSymmetry,
+}
+
+impl MarketHeader {
+    pub fn coordinate() -> MarketHeader {
+        MarketHeader {
+            format: MarketFormat::Coordinate,
+            field: MarketField::Real,
+            symmetry: MarketSymmetry::General,
+        }
+    }
+
+    pub fn array() -> MarketHeader {
+        MarketHeader {
+            format: MarketFormat::Array,
+            ..MarketHeader::coordinate()
+        }
+    }
+
+    fn parse(line: &str) -> Result<MarketHeader, String> {
+        let words: Vec<String> = line.split_whitespace().map(str::to_lowercase).collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+        let [banner, "matrix", format, field, symmetry] = words[..] else {
+            return Err(format!(
+                "expected '{} matrix <format> <field> <symmetry>'",
+                BANNER
+            ));
+        };
+        if banner != BANNER.to_lowercase() {
+            return Err(format!("expected '{}'", BANNER));
+        }
+
+        let format = match format {
+            "coordinate" => MarketFormat::Coordinate,
+            "array" => MarketFormat::Array,
+            _ => return Err(format!("unknown format '{}'", format)),
+        };
+        let field = match field {
+            "real" | "double" => MarketField::Real,
+            "integer" => MarketField::Integer,
+            "pattern" if format == MarketFormat::Coordinate => MarketField::Pattern,
+            "pattern" => return Err("the array format has no pattern field".to_string()),
+            _ => return Err(format!("unsupported field '{}'", field)),
+        };
+        let symmetry = match symmetry {
+            "general" => MarketSymmetry::General,
+            "symmetric" => MarketSymmetry::Symmetric,
+            _ => return Err(format!("unsupported symmetry '{}'", symmetry)),
+        };
+
+        Ok(MarketHeader {
+            format,
+            field,
+            symmetry,
+        })
+    }
+}
+
+impl fmt::Display for MarketHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self.format {
+            MarketFormat::Coordinate => "coordinate",
+            MarketFormat::Array => "array",
+        };
+        let field = match self.field {
+            MarketField::Real => "real",
+            MarketField::Integer => "integer",
+            MarketField::Pattern => "pattern",
+        };
+        let symmetry = match self.symmetry {
+            MarketSymmetry::General => "general",
+            MarketSymmetry::Symmetric => "symmetric",
+        };
+        write!(f, "{} matrix {} {} {}", BANNER, format, field, symmetry)
+    }
+}
+
+/// Entries reserved up front, whatever the size line declares.
+const PREALLOCATED_ENTRIES: usize = 1 << 16;
+
+/// A parsed file: the header, the shape and every entry with 0-based
+/// indices. Symmetric files already have the upper triangle mirrored.
+struct Entries<T> {
+    header: MarketHeader,
+    rows: usize,
+    cols: usize,
+    /// Line of the size line, for errors about the size found later.
+    size_line: usize,
+    triplets: Vec<(usize, usize, T)>,
+}
+
+/// Lines with their 1-based number, skipping comments and blank lines.
+struct Lines<R> {
+    reader: R,
+    line: usize,
+    buffer: String,
+}
+
+impl<R: BufRead> Lines<R> {
+    /// Moves to the next line with data; `false` at the end of the file.
+    fn advance(&mut self) -> Result<bool, FormatError> {
+        loop {
+            self.buffer.clear();
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Ok(false);
+            }
+            self.line += 1;
+
+            let trimmed = self.buffer.trim();
+            if !trimmed.is_empty() && !trimmed.starts_with('%') {
+                return Ok(true);
+            }
+        }
+    }
+
+    fn current(&self) -> &str {
+        self.buffer.trim()
+    }
+
+    fn error(&self, message: impl Into<String>) -> FormatError {
+        FormatError::Parse {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+}
+
+fn parse_number<N: FromStr>(word: &str, what: &str) -> Result<N, String> {
+    word.parse()
+        .map_err(|_| format!("invalid {} '{}'", what, word))
+}
+
+fn read_entries<T, R>(reader: R) -> Result<Entries<T>, FormatError>
+where
+    T: Scalar + FromStr,
+    R: BufRead,
+{
+    let mut lines = Lines {
+        reader,
+        line: 0,
+        buffer: String::new(),
+    };
+
+    // o banner começa com '%', então é lido antes de pular os comentários
+    lines.line = 1;
+    if lines.reader.read_line(&mut lines.buffer)? == 0 {
+        return Err(lines.error("empty file"));
+    }
+    let header = MarketHeader::parse(&lines.buffer).map_err(|message| lines.error(message))?;
+
+    if !lines.advance()? {
+        return Err(lines.error("missing size line"));
+    }
+    let size: Vec<usize> = lines
+        .current()
+        .split_whitespace()
+        .map(|word| parse_number(word, "size"))
+        .collect::<Result<_, _>>()
+        .map_err(|message| lines.error(message))?;
+    let (rows, cols, declared) = match (header.format, &size[..]) {
+        (MarketFormat::Coordinate, &[rows, cols, nnz]) => (rows, cols, nnz),
+        (MarketFormat::Array, &[rows, cols]) => match rows.checked_mul(cols) {
+            Some(len) => (rows, cols, len),
+            None => return Err(lines.error(format!("a {}x{} matrix is too large", rows, cols))),
+        },
+        (MarketFormat::Coordinate, _) => return Err(lines.error("expected 'rows cols entries'")),
+        (MarketFormat::Array, _) => return Err(lines.error("expected 'rows cols'")),
+    };
+
+    let symmetric = header.symmetry == MarketSymmetry::Symmetric;
+    if symmetric && rows != cols {
+        return Err(lines.error(format!(
+            "a symmetric matrix must be square, got {}x{}",
+            rows, cols
+        )));
+    }
+    let expected = match (header.format, symmetric) {
+        // n * (n + 1) / 2 sem estourar: `declared` já é n * n
+        (MarketFormat::Array, true) => declared / 2 + rows.div_ceil(2),
+        _ => declared,
+    };
+    let size_line = lines.line;
+
+    // o número de entradas vem do arquivo, então só uma parte é reservada
+    // antes de ler; o resto cresce com as entradas de verdade
+    let mut triplets = Vec::with_capacity(expected.min(PREALLOCATED_ENTRIES));
+    // posição do próximo valor no formato array
+    let (mut next_i, mut next_j) = (0, 0);
+
+    for count in 0..expected {
+        if !lines.advance()? {
+            return Err(lines.error(format!("expected {} entries, found {}", expected, count)));
+        }
+        let words: Vec<&str> = lines.current().split_whitespace().collect();
+
+        let (i, j, value) = match header.format {
+            MarketFormat::Coordinate => {
+                let (index, value) = match (header.field, &words[..]) {
+                    (MarketField::Pattern, [i, j]) => ([*i, *j], None),
+                    (MarketField::Pattern, _) => {
+                        return Err(lines.error("expected 'row col'"));
+                    }
+                    (_, [i, j, value]) => ([*i, *j], Some(*value)),
+                    (_, _) => return Err(lines.error("expected 'row col value'")),
+                };
+
+                let i: usize = parse_number(index[0], "row").map_err(|m| lines.error(m))?;
+                let j: usize = parse_number(index[1], "column").map_err(|m| lines.error(m))?;
+                if i == 0 || i > rows || j == 0 || j > cols {
+                    return Err(lines.error(format!(
+                        "entry ({}, {}) outside a {}x{} matrix",
+                        i, j, rows, cols
+                    )));
+                }
+                (i - 1, j - 1, value)
+            }
+            MarketFormat::Array => {
+                let [value] = words[..] else {
+                    return Err(lines.error("expected one value per line"));
+                };
+                let position = (next_i, next_j);
+                next_i += 1;
+                if next_i == rows {
+                    next_j += 1;
+                    next_i = if symmetric { next_j } else { 0 };
+                }
+                (position.0, position.1, Some(value))
+            }
+        };
+
+        let value = match value {
+            Some(word) => parse_number(word, "value").map_err(|m| lines.error(m))?,
+            None => T::one(),
+        };
+
+        triplets.push((i, j, value));
+        if symmetric && i != j {
+            triplets.push((j, i, value));
+        }
+    }
+
+    if lines.advance()? {
+        return Err(lines.error(format!("more than the {} declared entries", expected)));
+    }
+
+    Ok(Entries {
+        header,
+        rows,
+        cols,
+        size_line,
+        triplets,
+    })
+}
+
+/// Reads a file in either format as a sparse matrix. Zeros of the array
+/// format are dropped.
+pub fn read_coo<T, R>(reader: R) -> Result<CooMatrix<T>, FormatError>
+where
+    T: Scalar + FromStr,
+    R: BufRead,
+{
+    let entries = read_entries(reader)?;
+    let keep_zeros = entries.header.format == MarketFormat::Coordinate;
+
+    let mut coo = CooMatrix::new(entries.rows, entries.cols);
+    for (i, j, value) in entries.triplets {
+        if keep_zeros || value != T::zero() {
+            coo.push(i, j, value);
+        }
+    }
+    Ok(coo)
+}
+
+pub fn read_csr<T, R>(reader: R) -> Result<CsrMatrix<T>, FormatError>
+where
+    T: Scalar + FromStr,
+    R: BufRead,
+{
+    Ok(read_coo(reader)?.to_csr())
+}
+
+/// Reads a file in either format as `(rows, cols, data)`, with `data`
+/// row-major. Repeated coordinate entries are summed.
+pub fn read_dense<T, R>(reader: R) -> Result<(usize, usize, Vec<T>), FormatError>
+where
+    T: Scalar + FromStr,
+    R: BufRead,
+{
+    let entries = read_entries(reader)?;
+
+    let len = entries
+        .rows
+        .checked_mul(entries.cols)
+        .ok_or_else(|| FormatError::Parse {
+            line: entries.size_line,
+            message: format!(
+                "a {}x{} matrix is too large to store dense",
+                entries.rows, entries.cols
+            ),
+        })?;
+    let mut data = vec![T::zero(); len];
+    for (i, j, value) in entries.triplets {
+        data[i * entries.cols + j] += value;
+    }
+    Ok((entries.rows, entries.cols, data))
+}
+
+/// Writes a row-major `rows x cols` matrix. The coordinate format lists the
+/// entries that are not zero.
+pub fn write_dense<T, W>(
+    writer: W,
+    header: MarketHeader,
+    rows: usize,
+    cols: usize,
+    data: &[T],
+) -> Result<(), FormatError>
+where
+    T: Scalar + LowerExp,
+    W: Write,
+{
+    if data.len() != rows * cols {
+        return Err(FormatError::Matrix(MatrixError::InvalidDataLength {
+            expected: rows * cols,
+            actual: data.len(),
+        }));
+    }
+
+    match header.format {
+        MarketFormat::Coordinate => {
+            let csr = CsrMatrix::from_dense(data, rows, cols);
+            write_coordinate(writer, header, &csr)
+        }
+        MarketFormat::Array => write_array(writer, header, rows, cols, data),
+    }
+}
+
+/// Writes a sparse matrix in the coordinate format, whatever
+/// `header.format` says.
+pub fn write_csr<T, W>(
+    writer: W,
+    header: MarketHeader,
+    csr: &CsrMatrix<T>,
+) -> Result<(), FormatError>
+where
+    T: Scalar + LowerExp,
+    W: Write,
+{
+    let header = MarketHeader {
+        format: MarketFormat::Coordinate,
+        ..header
+    };
+    write_coordinate(writer, header, csr)
+}
+
+/// Reals in exponent notation (`2.5e-1`), which is exact and short for any
+/// magnitude; integers as they are; nothing for patterns.
+fn write_value<T: Scalar + LowerExp, W: Write>(
+    writer: &mut W,
+    field: MarketField,
+    value: T,
+) -> Result<(), FormatError> {
+    match field {
+        MarketField::Real => write!(writer, "{:e}", value)?,
+        MarketField::Integer => write!(writer, "{}", value)?,
+        MarketField::Pattern => {}
+    }
+    Ok(())
+}
+
+fn check_symmetric<T: Scalar>(
+    rows: usize,
+    cols: usize,
+    get: impl Fn(usize, usize) -> T,
+) -> Result<(), FormatError> {
+    if rows != cols {
+        return Err(FormatError::Matrix(MatrixError::NotSquare {
+            op: "write as symmetric",
+            rows,
+            cols,
+        }));
+    }
+    for i in 0..rows {
+        for j in 0..i {
+            if get(i, j) != get(j, i) {
+                return Err(FormatError::Matrix(MatrixError::NotSymmetric));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_coordinate<T, W>(
+    mut writer: W,
+    header: MarketHeader,
+    csr: &CsrMatrix<T>,
+) -> Result<(), FormatError>
+where
+    T: Scalar + LowerExp,
+    W: Write,
+{
+    let symmetric = header.symmetry == MarketSymmetry::Symmetric;
+    if symmetric {
+        check_symmetric(csr.rows(), csr.cols(), |i, j| csr.get(i, j))?;
+    }
+
+    // no simétrico só vai o triângulo de baixo
+    let lower = |i: usize, j: usize| !symmetric || j <= i;
+    let nnz = (0..csr.rows())
+        .map(|i| csr.row(i).0.iter().filter(|&&j| lower(i, j)).count())
+        .sum::<usize>();
+
+    writeln!(writer, "{}", header)?;
+    writeln!(writer, "{} {} {}", csr.rows(), csr.cols(), nnz)?;
+    for i in 0..csr.rows() {
+        let (cols, values) = csr.row(i);
+        for (&j, &value) in cols.iter().zip(values) {
+            if lower(i, j) {
+                write!(writer, "{} {}", i + 1, j + 1)?;
+                if header.field != MarketField::Pattern {
+                    write!(writer, " ")?;
+                    write_value(&mut writer, header.field, value)?;
+                }
+                writeln!(writer)?;
+            }
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_array<T, W>(
+    mut writer: W,
+    header: MarketHeader,
+    rows: usize,
+    cols: usize,
+    data: &[T],
+) -> Result<(), FormatError>
+where
+    T: Scalar + LowerExp,
+    W: Write,
+{
+    if header.field == MarketField::Pattern {
+        return Err(FormatError::Unsupported(
+            "the array format has no pattern field",
+        ));
+    }
+    let symmetric = header.symmetry == MarketSymmetry::Symmetric;
+    if symmetric {
+        check_symmetric(rows, cols, |i, j| data[i * cols + j])?;
+    }
+
+    writeln!(writer, "{}", header)?;
+    writeln!(writer, "{} {}", rows, cols)?;
+    for j in 0..cols {
+        let first_row = if symmetric { j } else { 0 };
+        for i in first_row..rows {
+            write_value(&mut writer, header.field, data[i * cols + j])?;
+            writeln!(writer)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
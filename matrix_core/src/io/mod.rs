@@ -0,0 +1,108 @@
+// Leitura e escrita de matrizes em arquivos. Cada formato fica num
+// submódulo e trabalha com `Read`/`Write` e buffers row-major, para que
+// todos os crates usem o mesmo código.
+
+pub mod binary;
+pub mod csv;
+pub mod market;
+pub mod npy;
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+use crate::MatrixError;
+
+/// Reasons a matrix file can't be read or written.
+#[derive(Debug)]
+pub enum FormatError {
+    Io(io::Error),
+    /// A line of a text file could not be understood. Lines start at 1.
+    Parse {
+        line: usize,
+        message: String,
+    },
+    /// A cell of a delimited text file is not a number. Row and column are
+    /// 1-based, as in a spreadsheet.
+    Cell {
+        row: usize,
+        col: usize,
+        message: String,
+    },
+    /// The header of a binary file is malformed or describes something
+    /// this crate does not read, e.g. an unknown dtype.
+    InvalidHeader(String),
+    /// The data does not match the checksum stored with it.
+    ChecksumMismatch {
+        expected: u32,
+        actual: u32,
+    },
+    /// The format can't represent what was asked, e.g. a dense pattern
+    /// matrix.
+    Unsupported(&'static str),
+    /// The file was read but the matrix it describes is invalid, or the
+    /// matrix does not fit the requested layout.
+    Matrix(MatrixError),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Io(e) => write!(f, "I/O error. {}", e),
+            FormatError::Parse { line, message } => {
+                write!(f, "Parse error at line {}. {}", line, message)
+            }
+            FormatError::Cell { row, col, message } => {
+                write!(
+                    f,
+                    "Invalid cell at row {}, column {}. {}",
+                    row, col, message
+                )
+            }
+            FormatError::InvalidHeader(message) => write!(f, "Invalid header. {}", message),
+            FormatError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Checksum mismatch. expected {:08x} & got {:08x}",
+                expected, actual
+            ),
+            FormatError::Unsupported(what) => write!(f, "Unsupported. {}", what),
+            FormatError::Matrix(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for FormatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FormatError::Io(e) => Some(e),
+            FormatError::Matrix(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FormatError {
+    fn from(e: io::Error) -> FormatError {
+        FormatError::Io(e)
+    }
+}
+
+impl From<MatrixError> for FormatError {
+    fn from(e: MatrixError) -> FormatError {
+        FormatError::Matrix(e)
+    }
+}
+
+/// Reads `len` bytes, growing the buffer as they arrive instead of trusting
+/// a length taken from the file.
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, FormatError> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(FormatError::Io(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("expected {} bytes, found {}", len, bytes.len()),
+        )));
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,494 @@
+// Formato `.npy` do NumPy e arquivos `.npz` sem compressão.
+//
+// Um `.npy` é:
+//
+//     \x93NUMPY <major> <minor> <tamanho do cabeçalho> <cabeçalho> <dados>
+//
+// O tamanho do cabeçalho tem 2 bytes na versão 1 e 4 nas versões 2 e 3. O
+// cabeçalho é um dict do Python em texto,
+//
+//     {'descr': '<f8', 'fortran_order': False, 'shape': (3, 4), }
+//
+// completado com espaços e um '\n' para que os dados comecem num múltiplo
+// de 64 bytes. Com `fortran_order` os dados estão por coluna.
+//
+// Um `.npz` (`numpy.savez`) é um zip com um `.npy` por array, guardado sem
+// compressão. O `numpy.savez_compressed` usa deflate, que não é lido aqui.
+
+use std::io::{Read, Write};
+
+use super::{read_bytes, FormatError};
+use crate::{MatrixError, Real};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+/// Alignment of the data, as in `numpy.lib.format.ARRAY_ALIGN`.
+const ARRAY_ALIGN: usize = 64;
+/// Elements converted per `write_all` when writing the data.
+const WRITE_CHUNK: usize = 8192;
+
+/// Element types with a NumPy dtype. Reading accepts either float width and
+/// converts it to `Self`.
+pub trait NpyElement: Real {
+    /// The `descr` written to the header.
+    const DESCR: &'static str;
+
+    fn extend_le_bytes(self, out: &mut Vec<u8>);
+}
+
+impl NpyElement for f64 {
+    const DESCR: &'static str = "<f8";
+
+    fn extend_le_bytes(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl NpyElement for f32 {
+    const DESCR: &'static str = "<f4";
+
+    fn extend_le_bytes(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+/// One array of a `.npz`, named as in `numpy.savez(file, name=array)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NpzArray<T> {
+    pub name: String,
+    pub rows: usize,
+    pub cols: usize,
+    pub data: Vec<T>,
+}
+
+struct Header {
+    item_size: usize,
+    fortran_order: bool,
+    rows: usize,
+    cols: usize,
+}
+
+fn invalid(message: impl Into<String>) -> FormatError {
+    FormatError::InvalidHeader(message.into())
+}
+
+/// The text after `'key':` in the header dict.
+fn dict_value<'a>(dict: &'a str, key: &str) -> Result<&'a str, FormatError> {
+    [format!("'{}'", key), format!("\"{}\"", key)]
+        .iter()
+        .find_map(|quoted| {
+            dict.find(quoted.as_str())
+                .map(|at| &dict[at + quoted.len()..])
+        })
+        .and_then(|rest| rest.trim_start().strip_prefix(':'))
+        .map(str::trim_start)
+        .ok_or_else(|| invalid(format!("missing '{}'", key)))
+}
+
+fn parse_header(dict: &str) -> Result<Header, FormatError> {
+    let descr = dict_value(dict, "descr")?;
+    let descr = descr
+        .strip_prefix('\'')
+        .or_else(|| descr.strip_prefix('"'))
+        .and_then(|rest| rest.split(['\'', '"']).next())
+        .ok_or_else(|| invalid("'descr' is not a string"))?;
+    let item_size = match descr {
+        "<f8" => 8,
+        "<f4" => 4,
+        _ => {
+            return Err(invalid(format!(
+                "unsupported dtype '{}', expected '<f8' or '<f4'",
+                descr
+            )))
+        }
+    };
+
+    let fortran_order = match dict_value(dict, "fortran_order")? {
+        order if order.starts_with("True") => true,
+        order if order.starts_with("False") => false,
+        _ => return Err(invalid("'fortran_order' is not True or False")),
+    };
+
+    let shape = dict_value(dict, "shape")?;
+    let shape = shape
+        .strip_prefix('(')
+        .and_then(|rest| rest.split(')').next())
+        .ok_or_else(|| invalid("'shape' is not a tuple"))?;
+    let shape: Vec<usize> = shape
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| {
+            dim.parse()
+                .map_err(|_| invalid(format!("invalid dimension '{}'", dim)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    // escalar vira 1x1 e vetor vira coluna
+    let (rows, cols) = match shape[..] {
+        [] => (1, 1),
+        [rows] => (rows, 1),
+        [rows, cols] => (rows, cols),
+        _ => {
+            return Err(invalid(format!(
+                "only 1-D and 2-D arrays are supported, got {} dimensions",
+                shape.len()
+            )))
+        }
+    };
+
+    Ok(Header {
+        item_size,
+        fortran_order,
+        rows,
+        cols,
+    })
+}
+
+/// Reads a `.npy` as `(rows, cols, data)`, with `data` row-major. 1-D
+/// arrays become columns.
+pub fn read<T, R>(mut reader: R) -> Result<(usize, usize, Vec<T>), FormatError>
+where
+    T: NpyElement,
+    R: Read,
+{
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(invalid("not a .npy file"));
+    }
+
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        major => return Err(invalid(format!("unsupported version {}", major))),
+    };
+
+    let dict = read_bytes(&mut reader, header_len)?;
+    let dict = String::from_utf8(dict).map_err(|_| invalid("header is not text"))?;
+    let header = parse_header(&dict)?;
+
+    let len = header
+        .rows
+        .checked_mul(header.cols)
+        .filter(|len| len.checked_mul(header.item_size).is_some())
+        .ok_or_else(|| invalid("'shape' too large for this platform"))?;
+    let bytes = read_bytes(&mut reader, len * header.item_size)?;
+
+    let values: Vec<T> = match header.item_size {
+        8 => bytes
+            .chunks_exact(8)
+            .map(|b| T::from_f64(f64::from_le_bytes(b.try_into().unwrap())))
+            .collect(),
+        _ => bytes
+            .chunks_exact(4)
+            .map(|b| T::from_f64(f32::from_le_bytes(b.try_into().unwrap()) as f64))
+            .collect(),
+    };
+
+    let (rows, cols) = (header.rows, header.cols);
+    if !header.fortran_order {
+        return Ok((rows, cols, values));
+    }
+
+    let mut data = Vec::with_capacity(len);
+    for i in 0..rows {
+        data.extend((0..cols).map(|j| values[j * rows + i]));
+    }
+    Ok((rows, cols, data))
+}
+
+/// Writes a row-major `rows x cols` matrix as a C-order `.npy`, with the
+/// header NumPy itself writes (version 1 unless the header does not fit).
+pub fn write<T, W>(mut writer: W, rows: usize, cols: usize, data: &[T]) -> Result<(), FormatError>
+where
+    T: NpyElement,
+    W: Write,
+{
+    if data.len() != rows * cols {
+        return Err(FormatError::Matrix(MatrixError::InvalidDataLength {
+            expected: rows * cols,
+            actual: data.len(),
+        }));
+    }
+
+    let dict = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': ({}, {}), }}",
+        T::DESCR,
+        rows,
+        cols
+    );
+    // preâmbulo + dict + '\n', completado até o próximo múltiplo de 64
+    let padded = |len_size: usize| {
+        let used = MAGIC.len() + 2 + len_size + dict.len() + 1;
+        dict.len() + 1 + ARRAY_ALIGN - used % ARRAY_ALIGN
+    };
+
+    writer.write_all(MAGIC)?;
+    let header_len = if padded(2) <= u16::MAX as usize {
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(padded(2) as u16).to_le_bytes())?;
+        padded(2)
+    } else {
+        writer.write_all(&[2, 0])?;
+        writer.write_all(&(padded(4) as u32).to_le_bytes())?;
+        padded(4)
+    };
+    writer.write_all(dict.as_bytes())?;
+    writer.write_all(" ".repeat(header_len - dict.len() - 1).as_bytes())?;
+    writer.write_all(b"\n")?;
+
+    let mut bytes = Vec::with_capacity(WRITE_CHUNK * 8);
+    for chunk in data.chunks(WRITE_CHUNK) {
+        bytes.clear();
+        for &value in chunk {
+            value.extend_le_bytes(&mut bytes);
+        }
+        writer.write_all(&bytes)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// Zip mínimo: só entradas sem compressão (método 0), lidas pelo diretório
+// central, que tem os tamanhos certos mesmo quando o cabeçalho local não
+// tem. As entradas de `numpy.savez` usam as extensões zip64.
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const ZIP64_END_LOCATOR: u32 = 0x0706_4b50;
+const ZIP64_END: u32 = 0x0606_4b50;
+const ZIP64_EXTRA: u16 = 0x0001;
+/// 1980-01-01 in the MS-DOS date format, the earliest date a zip can hold.
+const DOS_EPOCH: u16 = (1 << 5) | 1;
+
+/// `at + by` for positions and sizes taken from the archive.
+fn advance(at: usize, by: u64) -> Result<usize, FormatError> {
+    usize::try_from(by)
+        .ok()
+        .and_then(|by| at.checked_add(by))
+        .ok_or_else(|| invalid("truncated zip archive"))
+}
+
+fn read_le<const N: usize>(bytes: &[u8], at: usize) -> Result<[u8; N], FormatError> {
+    bytes
+        .get(at..advance(at, N as u64)?)
+        .map(|b| b.try_into().unwrap())
+        .ok_or_else(|| invalid("truncated zip archive"))
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> Result<u16, FormatError> {
+    read_le(bytes, at).map(u16::from_le_bytes)
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> Result<u32, FormatError> {
+    read_le(bytes, at).map(u32::from_le_bytes)
+}
+
+fn u64_at(bytes: &[u8], at: usize) -> Result<u64, FormatError> {
+    read_le(bytes, at).map(u64::from_le_bytes)
+}
+
+/// Offset and number of entries of the central directory.
+fn central_directory(zip: &[u8]) -> Result<(usize, usize), FormatError> {
+    // o registro final tem 22 bytes mais um comentário de até 64 KiB
+    let last = zip
+        .len()
+        .checked_sub(22)
+        .ok_or_else(|| invalid("not a zip archive"))?;
+    let end = (last.saturating_sub(u16::MAX as usize)..=last)
+        .rev()
+        .find(|&at| u32_at(zip, at).ok() == Some(END_OF_CENTRAL_DIRECTORY))
+        .ok_or_else(|| invalid("not a zip archive"))?;
+
+    let entries = u16_at(zip, end + 10)?;
+    let offset = u32_at(zip, end + 16)?;
+    if entries != u16::MAX && offset != u32::MAX {
+        return Ok((offset as usize, entries as usize));
+    }
+
+    let locator = end
+        .checked_sub(20)
+        .filter(|&at| u32_at(zip, at).ok() == Some(ZIP64_END_LOCATOR))
+        .ok_or_else(|| invalid("missing zip64 end of central directory"))?;
+    let end64 = advance(0, u64_at(zip, locator + 8)?)?;
+    if u32_at(zip, end64)? != ZIP64_END {
+        return Err(invalid("missing zip64 end of central directory"));
+    }
+    Ok((
+        advance(0, u64_at(zip, advance(end64, 48)?)?)?,
+        advance(0, u64_at(zip, advance(end64, 32)?)?)?,
+    ))
+}
+
+/// Reads every array of an uncompressed `.npz`, in archive order, with the
+/// `.npy` suffix dropped from the names.
+pub fn read_npz<T, R>(mut reader: R) -> Result<Vec<NpzArray<T>>, FormatError>
+where
+    T: NpyElement,
+    R: Read,
+{
+    let mut zip = Vec::new();
+    reader.read_to_end(&mut zip)?;
+
+    let (mut at, entries) = central_directory(&zip)?;
+    // `entries` vem do arquivo: o vetor cresce com as entradas lidas
+    let mut arrays = Vec::new();
+
+    for _ in 0..entries {
+        if u32_at(&zip, at)? != CENTRAL_HEADER {
+            return Err(invalid("bad central directory entry"));
+        }
+        let method = u16_at(&zip, at + 10)?;
+        let crc = u32_at(&zip, at + 16)?;
+        let compressed = u32_at(&zip, at + 20)?;
+        let uncompressed = u32_at(&zip, at + 24)?;
+        let name_len = u16_at(&zip, at + 28)? as usize;
+        let extra_len = u16_at(&zip, at + 30)? as usize;
+        let comment_len = u16_at(&zip, at + 32)? as usize;
+        let local_offset = u32_at(&zip, at + 42)?;
+
+        let name_start = at + 46;
+        let name = zip
+            .get(name_start..name_start + name_len)
+            .ok_or_else(|| invalid("truncated zip archive"))?;
+        let name = String::from_utf8_lossy(name).into_owned();
+
+        if method != 0 {
+            return Err(FormatError::Unsupported(
+                "compressed .npz, save it with numpy.savez instead of savez_compressed",
+            ));
+        }
+
+        // no zip64 os campos cheios (0xFFFFFFFF) vêm no extra, nesta ordem:
+        // tamanho original, tamanho comprimido, posição do cabeçalho local
+        let mut size = compressed as u64;
+        let mut local = local_offset as u64;
+        let mut extra = name_start + name_len;
+        let extra_end = extra + extra_len;
+        while extra + 4 <= extra_end {
+            let id = u16_at(&zip, extra)?;
+            let len = u16_at(&zip, extra + 2)? as usize;
+            if id == ZIP64_EXTRA {
+                let mut field = extra + 4;
+                if uncompressed == u32::MAX {
+                    field += 8;
+                }
+                if compressed == u32::MAX {
+                    size = u64_at(&zip, field)?;
+                    field += 8;
+                }
+                if local_offset == u32::MAX {
+                    local = u64_at(&zip, field)?;
+                }
+            }
+            extra += 4 + len;
+        }
+
+        let local = advance(0, local)?;
+        if u32_at(&zip, local)? != LOCAL_HEADER {
+            return Err(invalid(format!("bad local header for '{}'", name)));
+        }
+        let local_name_len = u16_at(&zip, advance(local, 26)?)? as u64;
+        let local_extra_len = u16_at(&zip, advance(local, 28)?)? as u64;
+        let data_start = advance(advance(local, 30)?, local_name_len + local_extra_len)?;
+        let data = zip
+            .get(data_start..advance(data_start, size)?)
+            .ok_or_else(|| invalid("truncated zip archive"))?;
+
+        let actual = crc32fast::hash(data);
+        if actual != crc {
+            return Err(FormatError::ChecksumMismatch {
+                expected: crc,
+                actual,
+            });
+        }
+
+        let (rows, cols, data) = read(data)?;
+        arrays.push(NpzArray {
+            name: name.strip_suffix(".npy").unwrap_or(&name).to_string(),
+            rows,
+            cols,
+            data,
+        });
+
+        at = extra_end + comment_len;
+    }
+
+    Ok(arrays)
+}
+
+/// Writes `(name, rows, cols, data)` arrays as an uncompressed `.npz`, the
+/// same archive `numpy.savez(file, name=array, ...)` produces.
+pub fn write_npz<T, W>(
+    mut writer: W,
+    arrays: &[(&str, usize, usize, &[T])],
+) -> Result<(), FormatError>
+where
+    T: NpyElement,
+    W: Write,
+{
+    let too_large = || FormatError::Unsupported("a .npz over 4 GiB");
+    let mut central = Vec::new();
+    let mut offset = 0usize;
+
+    for &(name, rows, cols, data) in arrays {
+        let mut npy = Vec::new();
+        write(&mut npy, rows, cols, data)?;
+        let name = format!("{}.npy", name);
+        let crc = crc32fast::hash(&npy);
+        let size = u32::try_from(npy.len()).map_err(|_| too_large())?;
+        let local = u32::try_from(offset).map_err(|_| too_large())?;
+
+        // campos comuns ao cabeçalho local e ao do diretório central:
+        // versão 2.0, sem flags, sem compressão, data, CRC e tamanhos
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&DOS_EPOCH.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+
+        writer.write_all(&LOCAL_HEADER.to_le_bytes())?;
+        writer.write_all(&common)?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&npy)?;
+        offset += 30 + name.len() + npy.len();
+
+        central.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes());
+        central.extend_from_slice(&common);
+        // comentário, disco, atributos internos e externos
+        central.extend_from_slice(&[0; 10]);
+        central.extend_from_slice(&local.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let entries = u16::try_from(arrays.len())
+        .map_err(|_| FormatError::Unsupported("a .npz with more than 65535 arrays"))?;
+    let central_offset = u32::try_from(offset).map_err(|_| too_large())?;
+
+    writer.write_all(&central)?;
+    writer.write_all(&END_OF_CENTRAL_DIRECTORY.to_le_bytes())?;
+    writer.write_all(&[0; 4])?;
+    writer.write_all(&entries.to_le_bytes())?;
+    writer.write_all(&entries.to_le_bytes())?;
+    writer.write_all(&(central.len() as u32).to_le_bytes())?;
+    writer.write_all(&central_offset.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
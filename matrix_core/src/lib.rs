@@ -0,0 +1,23 @@
+pub mod cache;
+mod complex;
+mod error;
+pub mod gemm;
+pub mod io;
+pub mod linalg;
+#[allow(clippy::module_inception)]
+mod linear_algebra_tests;
+mod operators;
+mod ops;
+mod scalar;
+pub mod simd;
+pub mod sparse;
+pub mod strassen;
+pub mod tuning;
+pub mod view;
+
+pub use complex::Complex;
+pub use error::MatrixError;
+pub use ops::MatrixOps;
+pub use scalar::{Real, Scalar};
+pub use sparse::{CooMatrix, CscMatrix, CsrMatrix};
+pub use view::{MatrixView, MatrixViewMut};
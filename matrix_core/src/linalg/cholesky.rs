@@ -0,0 +1,104 @@
+use crate::{MatrixError, Real};
+
+// Só o triângulo inferior de A é lido, como no `potrf` do LAPACK: o que
+// estiver acima da diagonal é ignorado e zerado no fator devolvido.
+
+/// Unblocked Cholesky `A = L * Lᵀ` of the row-major `n x n` matrix `a`,
+/// returning the lower triangular `L`.
+pub fn factor<T: Real>(mut a: Vec<T>, n: usize) -> Result<Vec<T>, MatrixError> {
+    factor_diagonal_block(&mut a, n, 0, n)?;
+    zero_upper(&mut a, n);
+
+    Ok(a)
+}
+
+/// Factors the diagonal block `k0..k1` in place, assuming the columns left
+/// of `k0` were already subtracted from it.
+pub fn factor_diagonal_block<T: Real>(
+    a: &mut [T],
+    n: usize,
+    k0: usize,
+    k1: usize,
+) -> Result<(), MatrixError> {
+    for j in k0..k1 {
+        let mut d = a[j * n + j];
+        for k in k0..j {
+            d = d - a[j * n + k] * a[j * n + k];
+        }
+
+        // `d` NaN também não é maior que zero
+        if d.partial_cmp(&T::zero()) != Some(std::cmp::Ordering::Greater) {
+            return Err(MatrixError::NotPositiveDefinite { pivot: j });
+        }
+
+        let d = d.sqrt();
+        a[j * n + j] = d;
+
+        for i in j + 1..k1 {
+            let mut sum = a[i * n + j];
+            for k in k0..j {
+                sum = sum - a[i * n + k] * a[j * n + k];
+            }
+            a[i * n + j] = sum / d;
+        }
+    }
+
+    Ok(())
+}
+
+/// `L21 = A21 * L11⁻ᵀ` for a single row below the diagonal block. `l` holds
+/// (at least) the rows `k0..k1` of the matrix, with `L11` already factored.
+pub fn solve_panel_row<T: Real>(row: &mut [T], l: &[T], n: usize, k0: usize, k1: usize) {
+    for j in k0..k1 {
+        let mut sum = row[j];
+        for k in k0..j {
+            sum = sum - row[k] * l[j * n + k];
+        }
+        row[j] = sum / l[j * n + j];
+    }
+}
+
+pub fn zero_upper<T: Real>(a: &mut [T], n: usize) {
+    for i in 0..n {
+        for j in i + 1..n {
+            a[i * n + j] = T::zero();
+        }
+    }
+}
+
+/// Solves `L * Lᵀ * X = B` for the row-major `n x nrhs` right-hand side `b`.
+pub fn solve<T: Real>(l: &[T], n: usize, b: &[T], nrhs: usize) -> Vec<T> {
+    let mut x = b.to_vec();
+
+    // L * y = b
+    for i in 0..n {
+        for k in 0..i {
+            let l_ik = l[i * n + k];
+            for c in 0..nrhs {
+                x[i * nrhs + c] = x[i * nrhs + c] - l_ik * x[k * nrhs + c];
+            }
+        }
+
+        let pivot = l[i * n + i];
+        for c in 0..nrhs {
+            x[i * nrhs + c] = x[i * nrhs + c] / pivot;
+        }
+    }
+
+    // Lᵀ * x = y
+    for i in (0..n).rev() {
+        for k in i + 1..n {
+            let l_ki = l[k * n + i];
+            for c in 0..nrhs {
+                x[i * nrhs + c] = x[i * nrhs + c] - l_ki * x[k * nrhs + c];
+            }
+        }
+
+        let pivot = l[i * n + i];
+        for c in 0..nrhs {
+            x[i * nrhs + c] = x[i * nrhs + c] / pivot;
+        }
+    }
+
+    x
+}
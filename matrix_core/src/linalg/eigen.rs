@@ -0,0 +1,389 @@
+use crate::{MatrixError, Real};
+
+/// Up to this size `symmetric_eigen` uses cyclic Jacobi, which is simpler
+/// and more accurate; above it the tridiagonal QR, which does less work.
+pub const JACOBI_MAX_SIZE: usize = 16;
+
+const MAX_JACOBI_SWEEPS: usize = 100;
+const MAX_QR_ITERATIONS: usize = 100;
+
+/// Eigenvalues in ascending order and, in column `i` of the row-major
+/// `n x n` `vectors`, the unit eigenvector of `values[i]`.
+#[derive(Clone, Debug)]
+pub struct SymmetricEigen<T> {
+    pub values: Vec<T>,
+    pub vectors: Vec<T>,
+}
+
+/// Checks that `a` is symmetric up to rounding and picks the method by size.
+pub fn symmetric_eigen<T: Real>(a: Vec<T>, n: usize) -> Result<SymmetricEigen<T>, MatrixError> {
+    let mut max = T::zero();
+    for &value in &a {
+        if value.abs() > max {
+            max = value.abs();
+        }
+    }
+
+    let tolerance = T::from_f64(n as f64) * T::epsilon() * max;
+    for i in 0..n {
+        for j in i + 1..n {
+            if (a[i * n + j] - a[j * n + i]).abs() > tolerance {
+                return Err(MatrixError::NotSymmetric);
+            }
+        }
+    }
+
+    if n <= JACOBI_MAX_SIZE {
+        jacobi(a, n)
+    } else {
+        tridiagonal_qr(a, n)
+    }
+}
+
+/// Cyclic Jacobi: sweeps every off-diagonal pair `(p, q)` zeroing it with a
+/// plane rotation until the off-diagonal part is negligible, or returns
+/// `NotConverged` after `MAX_JACOBI_SWEEPS` sweeps.
+pub fn jacobi<T: Real>(mut a: Vec<T>, n: usize) -> Result<SymmetricEigen<T>, MatrixError> {
+    let mut v = identity(n);
+
+    let mut norm_sqr = T::zero();
+    for &value in &a {
+        norm_sqr += value * value;
+    }
+    let tolerance = T::epsilon() * T::epsilon() * norm_sqr;
+
+    for sweep in 0..=MAX_JACOBI_SWEEPS {
+        let mut off = T::zero();
+        for i in 0..n {
+            for j in i + 1..n {
+                off += a[i * n + j] * a[i * n + j];
+            }
+        }
+
+        if off <= tolerance {
+            break;
+        }
+        // a última volta só confere; NaN também cai aqui
+        if sweep == MAX_JACOBI_SWEEPS {
+            return Err(MatrixError::NotConverged {
+                iterations: MAX_JACOBI_SWEEPS,
+            });
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq == T::zero() {
+                    continue;
+                }
+
+                // menor das duas raízes de t² + 2θt - 1 = 0, ângulo <= π/4
+                let theta = (a[q * n + q] - a[p * n + p]) / (T::from_f64(2.0) * apq);
+                let t = T::one() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                let t = if theta < T::zero() { -t } else { t };
+                let c = T::one() / (t * t + T::one()).sqrt();
+                let s = t * c;
+
+                // A = Jᵀ A J
+                for k in 0..n {
+                    let akp = a[k * n + p];
+                    let akq = a[k * n + q];
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let apk = a[p * n + k];
+                    let aqk = a[q * n + k];
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+                a[p * n + q] = T::zero();
+                a[q * n + p] = T::zero();
+
+                // V = V J
+                for k in 0..n {
+                    let vkp = v[k * n + p];
+                    let vkq = v[k * n + q];
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let values = (0..n).map(|i| a[i * n + i]).collect();
+    Ok(sorted(values, v, n))
+}
+
+/// Householder reduction to tridiagonal form followed by the shifted
+/// implicit QL iteration, the QR algorithm run from the top of the
+/// tridiagonal (`tred2` + `tql2` from EISPACK). Returns `NotConverged` when
+/// an eigenvalue takes more than `MAX_QR_ITERATIONS` steps.
+pub fn tridiagonal_qr<T: Real>(a: Vec<T>, n: usize) -> Result<SymmetricEigen<T>, MatrixError> {
+    if n == 0 {
+        return Ok(SymmetricEigen {
+            values: Vec::new(),
+            vectors: Vec::new(),
+        });
+    }
+
+    let mut v = a;
+    let mut d = vec![T::zero(); n];
+    let mut e = vec![T::zero(); n];
+
+    tridiagonalize(&mut v, &mut d, &mut e, n);
+    tridiagonal_ql(&mut v, &mut d, &mut e, n)?;
+
+    Ok(sorted(d, v, n))
+}
+
+/// Leaves the diagonal in `d`, the subdiagonal in `e[1..n]` and the
+/// accumulated orthogonal transformation in `v`.
+fn tridiagonalize<T: Real>(v: &mut [T], d: &mut [T], e: &mut [T], n: usize) {
+    for j in 0..n {
+        d[j] = v[(n - 1) * n + j];
+    }
+
+    for i in (1..n).rev() {
+        let mut scale = T::zero();
+        let mut h = T::zero();
+        for &x in &d[..i] {
+            scale += x.abs();
+        }
+
+        if scale == T::zero() {
+            e[i] = d[i - 1];
+            for j in 0..i {
+                d[j] = v[(i - 1) * n + j];
+                v[i * n + j] = T::zero();
+                v[j * n + i] = T::zero();
+            }
+        } else {
+            // vetor de Householder que zera a linha i à esquerda da subdiagonal
+            for x in &mut d[..i] {
+                *x = *x / scale;
+                h += *x * *x;
+            }
+
+            let f = d[i - 1];
+            let g = if f > T::zero() { -h.sqrt() } else { h.sqrt() };
+            e[i] = scale * g;
+            h = h - f * g;
+            d[i - 1] = f - g;
+            e[..i].fill(T::zero());
+
+            for j in 0..i {
+                let f = d[j];
+                v[j * n + i] = f;
+                let mut g = e[j] + v[j * n + j] * f;
+                for k in j + 1..i {
+                    g += v[k * n + j] * d[k];
+                    e[k] += v[k * n + j] * f;
+                }
+                e[j] = g;
+            }
+
+            let mut f = T::zero();
+            for j in 0..i {
+                e[j] = e[j] / h;
+                f += e[j] * d[j];
+            }
+
+            let hh = f / (h + h);
+            for j in 0..i {
+                e[j] = e[j] - hh * d[j];
+            }
+
+            for j in 0..i {
+                let f = d[j];
+                let g = e[j];
+                for k in j..i {
+                    v[k * n + j] = v[k * n + j] - (f * e[k] + g * d[k]);
+                }
+                d[j] = v[(i - 1) * n + j];
+                v[i * n + j] = T::zero();
+            }
+        }
+        d[i] = h;
+    }
+
+    // acumula as transformações
+    for i in 0..n - 1 {
+        v[(n - 1) * n + i] = v[i * n + i];
+        v[i * n + i] = T::one();
+
+        let h = d[i + 1];
+        if h != T::zero() {
+            for k in 0..=i {
+                d[k] = v[k * n + i + 1] / h;
+            }
+            for j in 0..=i {
+                let mut g = T::zero();
+                for k in 0..=i {
+                    g += v[k * n + i + 1] * v[k * n + j];
+                }
+                for k in 0..=i {
+                    v[k * n + j] = v[k * n + j] - g * d[k];
+                }
+            }
+        }
+
+        for k in 0..=i {
+            v[k * n + i + 1] = T::zero();
+        }
+    }
+
+    for j in 0..n {
+        d[j] = v[(n - 1) * n + j];
+        v[(n - 1) * n + j] = T::zero();
+    }
+    v[(n - 1) * n + n - 1] = T::one();
+    e[0] = T::zero();
+}
+
+fn tridiagonal_ql<T: Real>(
+    v: &mut [T],
+    d: &mut [T],
+    e: &mut [T],
+    n: usize,
+) -> Result<(), MatrixError> {
+    for i in 1..n {
+        e[i - 1] = e[i];
+    }
+    e[n - 1] = T::zero();
+
+    let two = T::from_f64(2.0);
+    let mut f = T::zero();
+    let mut tst1 = T::zero();
+
+    for l in 0..n {
+        let magnitude = d[l].abs() + e[l].abs();
+        if magnitude > tst1 {
+            tst1 = magnitude;
+        }
+
+        // procura um elemento da subdiagonal desprezível; um NaN nunca é,
+        // e acaba em NotConverged em vez de virar autovalor
+        let mut m = l;
+        while m < n - 1 {
+            if e[m].abs() <= T::epsilon() * tst1 {
+                break;
+            }
+            m += 1;
+        }
+
+        if m > l {
+            let mut converged = false;
+
+            for _ in 0..MAX_QR_ITERATIONS {
+                // deslocamento implícito
+                let g = d[l];
+                let mut p = (d[l + 1] - g) / (two * e[l]);
+                let mut r = hypot(p, T::one());
+                if p < T::zero() {
+                    r = -r;
+                }
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+                let dl1 = d[l + 1];
+                let mut h = g - d[l];
+                for x in &mut d[l + 2..n] {
+                    *x = *x - h;
+                }
+                f += h;
+
+                p = d[m];
+                let mut c = T::one();
+                let mut c2 = c;
+                let mut c3 = c;
+                let el1 = e[l + 1];
+                let mut s = T::zero();
+                let mut s2 = T::zero();
+
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    let g = c * e[i];
+                    h = c * p;
+                    r = hypot(p, e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+
+                    for k in 0..n {
+                        let h = v[k * n + i + 1];
+                        v[k * n + i + 1] = s * v[k * n + i] + c * h;
+                        v[k * n + i] = c * v[k * n + i] - s * h;
+                    }
+                }
+
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+
+                if e[l].abs() <= T::epsilon() * tst1 {
+                    converged = true;
+                    break;
+                }
+            }
+
+            if !converged {
+                return Err(MatrixError::NotConverged {
+                    iterations: MAX_QR_ITERATIONS,
+                });
+            }
+        }
+
+        d[l] += f;
+        e[l] = T::zero();
+    }
+
+    Ok(())
+}
+
+/// `sqrt(a² + b²)` without overflowing for large `a` or `b`.
+fn hypot<T: Real>(a: T, b: T) -> T {
+    let (a, b) = (a.abs(), b.abs());
+    let (big, small) = if a > b { (a, b) } else { (b, a) };
+
+    if big == T::zero() {
+        return T::zero();
+    }
+
+    let ratio = small / big;
+    big * (T::one() + ratio * ratio).sqrt()
+}
+
+fn identity<T: Real>(n: usize) -> Vec<T> {
+    let mut v = vec![T::zero(); n * n];
+    for i in 0..n {
+        v[i * n + i] = T::one();
+    }
+    v
+}
+
+/// Sorts the eigenvalues in ascending order, moving the columns of `vectors`
+/// along with them.
+fn sorted<T: Real>(values: Vec<T>, vectors: Vec<T>, n: usize) -> SymmetricEigen<T> {
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| {
+        values[i]
+            .partial_cmp(&values[j])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut sorted_vectors = vec![T::zero(); n * n];
+    for (column, &i) in order.iter().enumerate() {
+        for k in 0..n {
+            sorted_vectors[k * n + column] = vectors[k * n + i];
+        }
+    }
+
+    SymmetricEigen {
+        values: order.iter().map(|&i| values[i]).collect(),
+        vectors: sorted_vectors,
+    }
+}
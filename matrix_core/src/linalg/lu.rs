@@ -0,0 +1,202 @@
+use crate::{MatrixError, Real};
+
+/// LU factorization with partial pivoting, `P * A = L * U`, stored packed in
+/// a single row-major `n x n` buffer like LAPACK's `getrf`.
+#[derive(Clone, Debug)]
+pub struct LuFactors<T> {
+    pub n: usize,
+    /// L below the diagonal (its unit diagonal is implicit), U on and above it.
+    pub lu: Vec<T>,
+    /// Row `i` of `P * A` is row `permutation[i]` of `A`.
+    pub permutation: Vec<usize>,
+    /// Number of row swaps, gives the sign of the determinant.
+    pub swaps: usize,
+}
+
+impl<T: Real> LuFactors<T> {
+    /// Unblocked right-looking factorization of the row-major `n x n` matrix `a`.
+    pub fn factor(mut a: Vec<T>, n: usize) -> LuFactors<T> {
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let swaps = factor_panel(&mut a, n, 0, n, &mut permutation);
+
+        LuFactors {
+            n,
+            lu: a,
+            permutation,
+            swaps,
+        }
+    }
+
+    pub fn l(&self) -> Vec<T> {
+        let n = self.n;
+        let mut l = vec![T::zero(); n * n];
+
+        for i in 0..n {
+            l[i * n..i * n + i].copy_from_slice(&self.lu[i * n..i * n + i]);
+            l[i * n + i] = T::one();
+        }
+
+        l
+    }
+
+    pub fn u(&self) -> Vec<T> {
+        let n = self.n;
+        let mut u = vec![T::zero(); n * n];
+
+        for i in 0..n {
+            u[i * n + i..(i + 1) * n].copy_from_slice(&self.lu[i * n + i..(i + 1) * n]);
+        }
+
+        u
+    }
+
+    pub fn determinant(&self) -> T {
+        let n = self.n;
+        let mut det = if self.swaps.is_multiple_of(2) {
+            T::one()
+        } else {
+            -T::one()
+        };
+
+        for i in 0..n {
+            det = det * self.lu[i * n + i];
+        }
+
+        det
+    }
+
+    /// A pivot is treated as zero when it is below `n * eps` times the
+    /// largest entry of its column of U, the size of the rounding error
+    /// accumulated by the elimination. Each column is compared with itself,
+    /// so a badly scaled but regular matrix like `diag(1e20, 1)` passes.
+    pub fn is_singular(&self) -> bool {
+        let n = self.n;
+        let scale = T::from_f64(n as f64) * T::epsilon();
+
+        (0..n).any(|j| {
+            let mut max = T::zero();
+            for i in 0..=j {
+                let value = self.lu[i * n + j].abs();
+                if value > max {
+                    max = value;
+                }
+            }
+
+            self.lu[j * n + j].abs() <= scale * max
+        })
+    }
+
+    /// Solves `A * X = B` for the row-major `n x nrhs` right-hand side `b`.
+    pub fn solve(&self, b: &[T], nrhs: usize) -> Result<Vec<T>, MatrixError> {
+        if self.is_singular() {
+            return Err(MatrixError::Singular);
+        }
+
+        let n = self.n;
+        let mut x = vec![T::zero(); n * nrhs];
+
+        for i in 0..n {
+            let row = self.permutation[i];
+            x[i * nrhs..(i + 1) * nrhs].copy_from_slice(&b[row * nrhs..(row + 1) * nrhs]);
+        }
+
+        // L * y = P * b
+        for i in 0..n {
+            for k in 0..i {
+                let l = self.lu[i * n + k];
+                for c in 0..nrhs {
+                    x[i * nrhs + c] = x[i * nrhs + c] - l * x[k * nrhs + c];
+                }
+            }
+        }
+
+        // U * x = y
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                let u = self.lu[i * n + k];
+                for c in 0..nrhs {
+                    x[i * nrhs + c] = x[i * nrhs + c] - u * x[k * nrhs + c];
+                }
+            }
+
+            let pivot = self.lu[i * n + i];
+            for c in 0..nrhs {
+                x[i * nrhs + c] = x[i * nrhs + c] / pivot;
+            }
+        }
+
+        Ok(x)
+    }
+
+    pub fn inverse(&self) -> Result<Vec<T>, MatrixError> {
+        let n = self.n;
+        let mut identity = vec![T::zero(); n * n];
+
+        for i in 0..n {
+            identity[i * n + i] = T::one();
+        }
+
+        self.solve(&identity, n)
+    }
+}
+
+/// Factors the panel of columns `k0..k1` (all rows from `k0` down), swapping
+/// whole rows of `a` when pivoting. Columns to the right of the panel are
+/// only swapped, not updated, which is left to the caller in the blocked
+/// versions. Returns the number of swaps.
+pub fn factor_panel<T: Real>(
+    a: &mut [T],
+    n: usize,
+    k0: usize,
+    k1: usize,
+    permutation: &mut [usize],
+) -> usize {
+    let mut swaps = 0;
+
+    for k in k0..k1 {
+        let mut pivot_row = k;
+        for i in k + 1..n {
+            if a[i * n + k].abs() > a[pivot_row * n + k].abs() {
+                pivot_row = i;
+            }
+        }
+
+        if pivot_row != k {
+            for j in 0..n {
+                a.swap(k * n + j, pivot_row * n + j);
+            }
+            permutation.swap(k, pivot_row);
+            swaps += 1;
+        }
+
+        let pivot = a[k * n + k];
+        if pivot == T::zero() {
+            // coluna já zerada, nada a eliminar
+            continue;
+        }
+
+        for i in k + 1..n {
+            let l = a[i * n + k] / pivot;
+            a[i * n + k] = l;
+
+            for j in k + 1..k1 {
+                a[i * n + j] = a[i * n + j] - l * a[k * n + j];
+            }
+        }
+    }
+
+    swaps
+}
+
+/// Turns the block `A12` (rows `k0..k1`, columns `k1..n`) into `U12` by
+/// solving with the unit lower triangle `L11` of the factored panel.
+pub fn solve_panel_upper<T: Real>(a: &mut [T], n: usize, k0: usize, k1: usize) {
+    for k in k0..k1 {
+        for i in k + 1..k1 {
+            let l = a[i * n + k];
+            for j in k1..n {
+                a[i * n + j] = a[i * n + j] - l * a[k * n + j];
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+// Fatorações sobre o layout contíguo `Vec<T>` (row-major) usado pelos crates
+// contíguos. Os crates embrulham essas funções em métodos do `Matrix`, e os
+// tiled/paralelos trocam a parte cara pelas suas próprias versões.
+
+pub mod cholesky;
+pub mod eigen;
+pub mod lu;
+pub mod qr;
+pub mod svd;
@@ -0,0 +1,172 @@
+use crate::{MatrixError, Real};
+
+/// Householder QR of a row-major `m x n` matrix, stored like LAPACK's
+/// `geqrf`: R on and above the diagonal, the reflector vectors below it.
+///
+/// Reflector `k` is `H_k = I - tau[k] * v * vᵀ` with `v[k] = 1` (implicit)
+/// and `v[k + 1..m]` stored in column `k` under the diagonal, so that
+/// `A = H_0 * H_1 * ... * H_{p-1} * R` with `p = min(m, n)`.
+#[derive(Clone, Debug)]
+pub struct QrFactors<T> {
+    pub rows: usize,
+    pub cols: usize,
+    pub qr: Vec<T>,
+    pub tau: Vec<T>,
+}
+
+impl<T: Real> QrFactors<T> {
+    pub fn factor(mut a: Vec<T>, m: usize, n: usize) -> QrFactors<T> {
+        let p = m.min(n);
+        let mut tau = vec![T::zero(); p];
+
+        for k in 0..p {
+            let mut norm_sqr = T::zero();
+            for i in k..m {
+                norm_sqr += a[i * n + k] * a[i * n + k];
+            }
+
+            let norm = norm_sqr.sqrt();
+            if norm == T::zero() {
+                // coluna já zerada, H_k = I
+                continue;
+            }
+
+            // sinal oposto ao de alpha para não cancelar em `alpha - beta`
+            let alpha = a[k * n + k];
+            let beta = if alpha > T::zero() { -norm } else { norm };
+            let v0 = alpha - beta;
+
+            for i in k + 1..m {
+                a[i * n + k] = a[i * n + k] / v0;
+            }
+            a[k * n + k] = beta;
+            tau[k] = (beta - alpha) / beta;
+
+            // aplica H_k nas colunas à direita
+            for j in k + 1..n {
+                let mut w = a[k * n + j];
+                for i in k + 1..m {
+                    w += a[i * n + k] * a[i * n + j];
+                }
+
+                let w = tau[k] * w;
+                a[k * n + j] = a[k * n + j] - w;
+                for i in k + 1..m {
+                    a[i * n + j] = a[i * n + j] - w * a[i * n + k];
+                }
+            }
+        }
+
+        QrFactors {
+            rows: m,
+            cols: n,
+            qr: a,
+            tau,
+        }
+    }
+
+    /// Thin Q, `m x min(m, n)` with orthonormal columns.
+    pub fn q(&self) -> Vec<T> {
+        let m = self.rows;
+        let p = self.tau.len();
+        let mut q = vec![T::zero(); m * p];
+
+        for i in 0..p {
+            q[i * p + i] = T::one();
+        }
+
+        for k in (0..p).rev() {
+            self.reflect(k, &mut q, p);
+        }
+
+        q
+    }
+
+    /// Upper triangular R, `min(m, n) x n`.
+    pub fn r(&self) -> Vec<T> {
+        let n = self.cols;
+        let p = self.tau.len();
+        let mut r = vec![T::zero(); p * n];
+
+        for i in 0..p {
+            r[i * n + i..(i + 1) * n].copy_from_slice(&self.qr[i * n + i..(i + 1) * n]);
+        }
+
+        r
+    }
+
+    /// Minimizes `||A * x - b||` column by column of the row-major
+    /// `m x nrhs` right-hand side `b`, returning the `n x nrhs` solution.
+    pub fn least_squares(&self, b: &[T], nrhs: usize) -> Result<Vec<T>, MatrixError> {
+        let m = self.rows;
+        let n = self.cols;
+        if m < n {
+            return Err(MatrixError::Underdetermined { rows: m, cols: n });
+        }
+
+        // cada R_jj é comparado com o maior elemento da coluna j de R, que
+        // tem a escala da coluna j de A: multiplicar uma coluna por uma
+        // constante não muda a resposta
+        let scale = T::from_f64(m as f64) * T::epsilon();
+        let dependent = (0..n).any(|j| {
+            let mut max = T::zero();
+            for i in 0..=j {
+                let value = self.qr[i * n + j].abs();
+                if value > max {
+                    max = value;
+                }
+            }
+
+            self.qr[j * n + j].abs() <= scale * max
+        });
+        if dependent {
+            return Err(MatrixError::Singular);
+        }
+
+        // y = Qᵀ * b
+        let mut y = b.to_vec();
+        for k in 0..n {
+            self.reflect(k, &mut y, nrhs);
+        }
+
+        // R * x = y[0..n]
+        y.truncate(n * nrhs);
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                let r = self.qr[i * n + k];
+                for c in 0..nrhs {
+                    y[i * nrhs + c] = y[i * nrhs + c] - r * y[k * nrhs + c];
+                }
+            }
+
+            let pivot = self.qr[i * n + i];
+            for c in 0..nrhs {
+                y[i * nrhs + c] = y[i * nrhs + c] / pivot;
+            }
+        }
+
+        Ok(y)
+    }
+
+    /// Applies `H_k` to every column of the row-major `m x cols` slice `x`.
+    fn reflect(&self, k: usize, x: &mut [T], cols: usize) {
+        let n = self.cols;
+        let tau = self.tau[k];
+        if tau == T::zero() {
+            return;
+        }
+
+        for c in 0..cols {
+            let mut w = x[k * cols + c];
+            for i in k + 1..self.rows {
+                w += self.qr[i * n + k] * x[i * cols + c];
+            }
+
+            let w = tau * w;
+            x[k * cols + c] = x[k * cols + c] - w;
+            for i in k + 1..self.rows {
+                x[i * cols + c] = x[i * cols + c] - w * self.qr[i * n + k];
+            }
+        }
+    }
+}
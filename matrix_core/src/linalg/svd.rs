@@ -0,0 +1,234 @@
+use crate::{MatrixError, Real};
+
+const MAX_SWEEPS: usize = 100;
+
+/// `A = U * diag(singular_values) * Vᵀ` for a row-major `m x n` matrix.
+///
+/// The singular values are non-negative and in descending order. The thin
+/// form has `U` as `m x p` and `Vᵀ` as `p x n` with `p = min(m, n)`; the full
+/// form completes them to `m x m` and `n x n`.
+#[derive(Clone, Debug)]
+pub struct Svd<T> {
+    pub rows: usize,
+    pub cols: usize,
+    pub u: Vec<T>,
+    pub u_cols: usize,
+    pub singular_values: Vec<T>,
+    pub vt: Vec<T>,
+    pub vt_rows: usize,
+}
+
+/// One-sided Jacobi (Hestenes): rotates pairs of columns of `A` until they
+/// are all orthogonal, accumulating the rotations in `V`. The column norms
+/// are then the singular values. It works on `Aᵀ` so that each column is a
+/// contiguous row. Returns `NotConverged` if the columns are still being
+/// rotated after `MAX_SWEEPS` sweeps.
+pub fn svd<T: Real>(a: &[T], m: usize, n: usize, full: bool) -> Result<Svd<T>, MatrixError> {
+    if m < n {
+        // Aᵀ = U' Σ V'ᵀ  =>  A = V' Σ U'ᵀ
+        let transposed = svd(&transpose(a, m, n), n, m, full)?;
+
+        return Ok(Svd {
+            rows: m,
+            cols: n,
+            u: transpose(&transposed.vt, transposed.vt_rows, m),
+            u_cols: transposed.vt_rows,
+            singular_values: transposed.singular_values,
+            vt: transpose(&transposed.u, n, transposed.u_cols),
+            vt_rows: transposed.u_cols,
+        });
+    }
+
+    // linhas de `w` são as colunas de A, linhas de `vt` as colunas de V
+    let mut w: Vec<Vec<T>> = (0..n)
+        .map(|j| (0..m).map(|i| a[i * n + j]).collect())
+        .collect();
+    let mut vt: Vec<Vec<T>> = (0..n).map(|j| unit(n, j)).collect();
+
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let alpha = dot(&w[i], &w[i]);
+                let beta = dot(&w[j], &w[j]);
+                let gamma = dot(&w[i], &w[j]);
+
+                if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                let zeta = (beta - alpha) / (T::from_f64(2.0) * gamma);
+                let t = T::one() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let t = if zeta < T::zero() { -t } else { t };
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+
+                rotate(&mut w, i, j, c, s);
+                rotate(&mut vt, i, j, c, s);
+            }
+        }
+
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+
+    // com NaN todo par é girado de novo, então também termina aqui
+    if !converged {
+        return Err(MatrixError::NotConverged {
+            iterations: MAX_SWEEPS,
+        });
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    let norms: Vec<T> = w.iter().map(|column| dot(column, column).sqrt()).collect();
+    order.sort_by(|&i, &j| {
+        norms[j]
+            .partial_cmp(&norms[i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let largest = order.first().map_or(T::zero(), |&i| norms[i]);
+    let tolerance = T::from_f64(m as f64) * T::epsilon() * largest;
+
+    // colunas de U para valores singulares desprezíveis são completadas depois
+    let mut u_columns: Vec<Vec<T>> = Vec::with_capacity(m);
+    let mut singular_values = Vec::with_capacity(n);
+    let mut vt_rows = Vec::with_capacity(n);
+    for &i in &order {
+        let sigma = norms[i];
+        singular_values.push(sigma);
+        vt_rows.push(vt[i].clone());
+
+        if sigma > tolerance {
+            u_columns.push(w[i].iter().map(|&x| x / sigma).collect());
+        }
+    }
+
+    let u_cols = if full { m } else { n };
+    complete_basis(&mut u_columns, m, u_cols);
+
+    Ok(Svd {
+        rows: m,
+        cols: n,
+        u: transpose(&u_columns.concat(), u_cols, m),
+        u_cols,
+        singular_values,
+        vt: vt_rows.concat(),
+        vt_rows: n,
+    })
+}
+
+impl<T: Real> Svd<T> {
+    /// Number of singular values above `tolerance`.
+    pub fn rank(&self, tolerance: T) -> usize {
+        self.singular_values
+            .iter()
+            .filter(|&&sigma| sigma > tolerance)
+            .count()
+    }
+
+    /// Tolerance used by `pseudo_inverse`, the same as NumPy's `pinv`:
+    /// `max(m, n) * eps * sigma_max`.
+    pub fn default_tolerance(&self) -> T {
+        let largest = self.singular_values.first().copied().unwrap_or(T::zero());
+        T::from_f64(self.rows.max(self.cols) as f64) * T::epsilon() * largest
+    }
+
+    /// Moore-Penrose pseudo-inverse `V * diag(1 / sigma) * Uᵀ` (`n x m`),
+    /// dropping the singular values below `default_tolerance`.
+    pub fn pseudo_inverse(&self) -> Vec<T> {
+        let (m, n) = (self.rows, self.cols);
+        let tolerance = self.default_tolerance();
+        let mut result = vec![T::zero(); n * m];
+
+        for (k, &sigma) in self.singular_values.iter().enumerate() {
+            if sigma <= tolerance {
+                break;
+            }
+
+            for i in 0..n {
+                let v = self.vt[k * n + i] / sigma;
+                for j in 0..m {
+                    result[i * m + j] += v * self.u[j * self.u_cols + k];
+                }
+            }
+        }
+
+        result
+    }
+
+    /// `sigma_max / sigma_min` in the 2-norm. Infinite only when `sigma_min`
+    /// is exactly zero; a rank deficient matrix usually gets a huge finite
+    /// value from rounding instead, so compare with `rank` for that.
+    pub fn condition_number(&self) -> T {
+        match (self.singular_values.first(), self.singular_values.last()) {
+            (Some(&largest), Some(&smallest)) if smallest > T::zero() => largest / smallest,
+            (Some(_), Some(_)) => T::from_f64(f64::INFINITY),
+            _ => T::zero(),
+        }
+    }
+}
+
+/// Adds vectors of the canonical basis, orthogonalized by Gram-Schmidt
+/// against the existing ones, until there are `count` orthonormal vectors.
+fn complete_basis<T: Real>(vectors: &mut Vec<Vec<T>>, len: usize, count: usize) {
+    let mut k = 0;
+
+    while vectors.len() < count && k < len {
+        let mut candidate = unit(len, k);
+        k += 1;
+
+        // duas passadas para não perder ortogonalidade
+        for _ in 0..2 {
+            for v in vectors.iter() {
+                let projection = dot(v, &candidate);
+                for (c, &x) in candidate.iter_mut().zip(v) {
+                    *c = *c - projection * x;
+                }
+            }
+        }
+
+        let norm = dot(&candidate, &candidate).sqrt();
+        if norm > T::from_f64(0.5) {
+            vectors.push(candidate.iter().map(|&x| x / norm).collect());
+        }
+    }
+}
+
+fn rotate<T: Real>(rows: &mut [Vec<T>], i: usize, j: usize, c: T, s: T) {
+    let (head, tail) = rows.split_at_mut(j);
+    for (x, y) in head[i].iter_mut().zip(tail[0].iter_mut()) {
+        let (xi, yj) = (*x, *y);
+        *x = c * xi - s * yj;
+        *y = s * xi + c * yj;
+    }
+}
+
+fn dot<T: Real>(a: &[T], b: &[T]) -> T {
+    let mut sum = T::zero();
+    for (&x, &y) in a.iter().zip(b) {
+        sum += x * y;
+    }
+    sum
+}
+
+fn unit<T: Real>(len: usize, k: usize) -> Vec<T> {
+    let mut v = vec![T::zero(); len];
+    v[k] = T::one();
+    v
+}
+
+fn transpose<T: Real>(a: &[T], rows: usize, cols: usize) -> Vec<T> {
+    let mut result = vec![T::zero(); rows * cols];
+    for i in 0..rows {
+        for j in 0..cols {
+            result[j * rows + i] = a[i * cols + j];
+        }
+    }
+    result
+}
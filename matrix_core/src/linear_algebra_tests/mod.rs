@@ -0,0 +1,1421 @@
+#[cfg(test)]
+mod linear_algebra_tests {
+    use crate::cache::{self, CacheLevel, CacheTopology};
+    use crate::gemm;
+    use crate::io::binary::{self, BinaryHeader, Dtype, Layout, MappedMatrix};
+    use crate::io::csv::{self, CsvFormat};
+    use crate::io::market::{self, MarketField, MarketHeader, MarketSymmetry};
+    use crate::io::npy::{self, NpzArray};
+    use crate::io::FormatError;
+    use crate::linalg::lu::LuFactors;
+    use crate::linalg::qr::QrFactors;
+    use crate::linalg::{cholesky, eigen, svd};
+    use crate::simd::{self, SimdLevel};
+    use crate::sparse::{CooMatrix, CscMatrix, CsrMatrix};
+    use crate::strassen;
+    use crate::tuning::{self, LoopOrder, Tuning};
+    use crate::view::{self, MatrixView, MatrixViewMut};
+    use crate::{MatrixError, Scalar};
+
+    // níveis que a CPU não tem caem para `simd::level()`, então todos podem
+    // ser pedidos
+    const ALL_LEVELS: [SimdLevel; 3] = [SimdLevel::Avx2Fma, SimdLevel::Sse2, SimdLevel::Scalar];
+
+    fn sequence(len: usize, offset: f64) -> Vec<f64> {
+        (0..len).map(|i| (i as f64) * 0.5 - offset).collect()
+    }
+
+    #[test]
+    fn simd_elementwise_matches_scalar_on_every_level() {
+        // 11 elements so both the vector loop and the scalar tail run
+        let a = sequence(11, 2.0);
+        let b = sequence(11, -1.0);
+
+        for level in ALL_LEVELS {
+            let mut added = vec![0.0; 11];
+            let mut subtracted = vec![0.0; 11];
+            let mut multiplied = vec![0.0; 11];
+            simd::add_with(level, &a, &b, &mut added);
+            simd::sub_with(level, &a, &b, &mut subtracted);
+            simd::mul_with(level, &a, &b, &mut multiplied);
+
+            for i in 0..11 {
+                assert_eq!(added[i], a[i] + b[i], "{:?}", level);
+                assert_eq!(subtracted[i], a[i] - b[i], "{:?}", level);
+                assert_eq!(multiplied[i], a[i] * b[i], "{:?}", level);
+            }
+        }
+    }
+
+    #[test]
+    fn gemm_matches_naive_on_every_level() {
+        let (m, n, k) = (13, 11, 9);
+        let a = sequence(m * k, 3.0);
+        let b = sequence(k * n, 7.0);
+
+        let mut expected = vec![0.0; m * n];
+        for i in 0..m {
+            for j in 0..n {
+                for p in 0..k {
+                    expected[i * n + j] += a[i * k + p] * b[p * n + j];
+                }
+            }
+        }
+
+        for level in ALL_LEVELS {
+            let mut c = vec![0.0; m * n];
+            gemm::gemm_with(level, m, n, k, &a, k, &b, n, &mut c, n);
+
+            for (value, expected_value) in c.iter().zip(&expected) {
+                assert!((value - expected_value).abs() < 1e-9, "{:?}", level);
+            }
+        }
+    }
+
+    #[test]
+    fn f32_micro_kernel_matches_scalar_on_every_level() {
+        let kc = 5;
+        let a_panel: Vec<f32> = (0..kc * gemm::MR).map(|x| (x % 7) as f32 - 3.0).collect();
+        let b_panel: Vec<f32> = (0..kc * gemm::NR).map(|x| (x % 5) as f32 - 2.0).collect();
+        // 3 x 7 válidos num bloco 4 x 8, para passar pelo `store` parcial
+        let (mr, nr) = (3, 7);
+
+        for level in ALL_LEVELS {
+            let mut c = vec![1.0f32; gemm::MR * gemm::NR];
+            simd::micro_kernel_f32_for(level)(kc, &a_panel, &b_panel, &mut c, gemm::NR, mr, nr);
+
+            for i in 0..gemm::MR {
+                for j in 0..gemm::NR {
+                    let expected = if i < mr && j < nr {
+                        1.0 + (0..kc)
+                            .map(|p| a_panel[p * gemm::MR + i] * b_panel[p * gemm::NR + j])
+                            .sum::<f32>()
+                    } else {
+                        1.0
+                    };
+                    assert_eq!(c[i * gemm::NR + j], expected, "{:?}", level);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gemm_on_integers_uses_the_portable_kernel() {
+        let (m, n, k) = (5, 10, 3);
+        let a: Vec<i64> = (0..(m * k) as i64).collect();
+        let b: Vec<i64> = (0..(k * n) as i64).map(|x| x - 7).collect();
+
+        let mut c = vec![0; m * n];
+        gemm::gemm(m, n, k, &a, k, &b, n, &mut c, n);
+
+        for i in 0..m {
+            for j in 0..n {
+                let expected: i64 = (0..k).map(|p| a[i * k + p] * b[p * n + j]).sum();
+                assert_eq!(c[i * n + j], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn lu_factors_solve_and_determinant() {
+        // [[1, 2], [3, 4]] pivota na segunda linha
+        let lu = LuFactors::<f64>::factor(vec![1.0, 2.0, 3.0, 4.0], 2);
+
+        assert_eq!(lu.permutation, vec![1, 0]);
+        assert!((lu.determinant() + 2.0).abs() < 1e-12);
+
+        let x = lu.solve(&[5.0, 11.0], 1).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 2.0).abs() < 1e-12);
+
+        let singular = LuFactors::factor(vec![1.0, 2.0, 2.0, 4.0], 2);
+        assert_eq!(singular.inverse().err(), Some(MatrixError::Singular));
+    }
+
+    #[test]
+    fn lu_accepts_badly_scaled_regular_matrices() {
+        let diagonal = LuFactors::<f64>::factor(vec![1e20, 0.0, 0.0, 1.0], 2);
+        assert!(!diagonal.is_singular());
+        let x = diagonal.solve(&[2e20, 3.0], 1).unwrap();
+        assert!((x[0] - 2.0).abs() < 1e-12 && (x[1] - 3.0).abs() < 1e-12);
+        let inverse = diagonal.inverse().unwrap();
+        assert!((inverse[0] - 1e-20).abs() < 1e-32 && (inverse[3] - 1.0).abs() < 1e-12);
+
+        // colunas em escalas diferentes: [[2e20, 1], [1e20, 3]] * [1e-20, 1]
+        let columns = LuFactors::<f64>::factor(vec![2e20, 1.0, 1e20, 3.0], 2);
+        let x = columns.solve(&[3.0, 4.0], 1).unwrap();
+        assert!((x[0] - 1e-20).abs() < 1e-32 && (x[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cholesky_factor_and_solve() {
+        let l = cholesky::factor::<f64>(vec![4.0, 2.0, 2.0, 5.0], 2).unwrap();
+        assert_eq!(l, vec![2.0, 0.0, 1.0, 2.0]);
+
+        let x = cholesky::solve(&l, 2, &[6.0, 7.0], 1);
+        assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12);
+
+        assert_eq!(
+            cholesky::factor(vec![1.0, 0.0, 0.0, -1.0], 2).err(),
+            Some(MatrixError::NotPositiveDefinite { pivot: 1 })
+        );
+    }
+
+    #[test]
+    fn qr_factors_of_2x2() {
+        // [[3, 1], [4, 2]]: a primeira coluna tem norma 5
+        let qr = QrFactors::<f64>::factor(vec![3.0, 1.0, 4.0, 2.0], 2, 2);
+        let r = qr.r();
+
+        assert!((r[0].abs() - 5.0).abs() < 1e-12);
+        assert_eq!(r[2], 0.0);
+        assert!(((r[0] * r[3]).abs() - 2.0).abs() < 1e-12);
+
+        let x = qr.least_squares(&[5.0, 8.0], 1).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn least_squares_accepts_columns_of_different_scales() {
+        // [[1e20, 1], [0, 1], [0, 1]]: posto 2, colunas em escalas distantes
+        let qr = QrFactors::<f64>::factor(vec![1e20, 1.0, 0.0, 1.0, 0.0, 1.0], 3, 2);
+        let x = qr.least_squares(&[1.0, 2.0, 2.0], 1).unwrap();
+        assert!((x[0] + 1e-20).abs() < 1e-32 && (x[1] - 2.0).abs() < 1e-12);
+
+        let dependent = QrFactors::<f64>::factor(vec![1e20, 2e20, 1.0, 2.0, 3.0, 6.0], 3, 2);
+        assert_eq!(
+            dependent.least_squares(&[1.0, 2.0, 3.0], 1).err(),
+            Some(MatrixError::Singular)
+        );
+    }
+
+    #[test]
+    fn jacobi_and_tridiagonal_qr_agree() {
+        let n = 20;
+        let mut a = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                a[i * n + j] = 1.0 / (1.0 + (i as f64 - j as f64).abs()) + (i * j) as f64 * 0.01;
+            }
+        }
+
+        let jacobi = eigen::jacobi(a.clone(), n).unwrap();
+        let qr = eigen::tridiagonal_qr(a, n).unwrap();
+
+        for (x, y) in jacobi.values.iter().zip(&qr.values) {
+            assert!((x - y).abs() < 1e-10, "{} != {}", x, y);
+        }
+    }
+
+    #[test]
+    fn eigen_reports_non_convergence() {
+        // NaN nunca fica abaixo da tolerância: os dois métodos esgotam o limite
+        for n in [3, 20] {
+            let mut a = vec![0.0; n * n];
+            for i in 0..n {
+                a[i * n + i] = i as f64;
+            }
+            a[1] = f64::NAN;
+            a[n] = f64::NAN;
+
+            assert!(matches!(
+                eigen::symmetric_eigen(a, n),
+                Err(MatrixError::NotConverged { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn assign_slices_cross_chunk_boundaries() {
+        // 1000 não é múltiplo do pedaço copiado para a pilha
+        let a: Vec<f64> = (0..1000).map(|i| i as f64 * 0.5).collect();
+        let b: Vec<f64> = (0..1000).map(|i| 3.0 - i as f64).collect();
+
+        let mut out = a.clone();
+        f64::add_assign_slices(&mut out, &b);
+        assert!(out.iter().zip(&a).zip(&b).all(|((&o, &x), &y)| o == x + y));
+        f64::sub_assign_slices(&mut out, &b);
+        assert_eq!(out, a);
+        f64::mul_assign_slices(&mut out, &b);
+        assert!(out.iter().zip(&a).zip(&b).all(|((&o, &x), &y)| o == x * y));
+
+        let mut ints: Vec<i32> = (0..1000).collect();
+        i32::sub_assign_slices(&mut ints, &(0..1000).collect::<Vec<_>>());
+        assert!(ints.iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    fn svd_of_row_vector() {
+        // [3, 4] tem um único valor singular, 5, e V₁ = [0.6, 0.8]
+        let svd = svd::svd::<f64>(&[3.0, 4.0], 1, 2, true).unwrap();
+
+        assert_eq!((svd.u_cols, svd.vt_rows), (1, 2));
+        assert!((svd.singular_values[0] - 5.0).abs() < 1e-12);
+        assert!((svd.vt[0].abs() - 0.6).abs() < 1e-12);
+        assert!((svd.vt[1].abs() - 0.8).abs() < 1e-12);
+        assert_eq!(svd.rank(1e-10), 1);
+
+        let pinv = svd.pseudo_inverse();
+        assert!((pinv[0] - 0.12).abs() < 1e-12 && (pinv[1] - 0.16).abs() < 1e-12);
+    }
+
+    #[test]
+    fn svd_reports_non_convergence() {
+        let a = [1.0, f64::NAN, 0.0, 2.0];
+
+        assert_eq!(
+            svd::svd(&a, 2, 2, false).err(),
+            Some(MatrixError::NotConverged { iterations: 100 })
+        );
+        // a forma larga passa pela transposta e propaga o erro
+        let wide = [1.0, f64::NAN, 0.0, 0.0, 2.0, 1.0];
+        assert!(svd::svd(&wide, 2, 3, false).is_err());
+    }
+
+    #[test]
+    fn tuning_round_trips_through_the_config_file() {
+        let path = std::env::temp_dir().join(format!("matrix_tuning_{}.conf", std::process::id()));
+        let tiled = Tuning {
+            block_size: 96,
+            loop_order: LoopOrder::Ikj,
+        };
+        let parallel = Tuning {
+            block_size: 128,
+            loop_order: LoopOrder::Ijk,
+        };
+
+        assert_eq!(tuning::load_from(&path, "contiguous_tiled"), None);
+        tuning::save_to(&path, "contiguous_tiled", tiled).unwrap();
+        tuning::save_to(&path, "contiguous_parallel_tiled", parallel).unwrap();
+        // salvar de novo substitui só a seção do crate
+        tuning::save_to(&path, "contiguous_tiled", tiled).unwrap();
+
+        assert_eq!(tuning::load_from(&path, "contiguous_tiled"), Some(tiled));
+        assert_eq!(
+            tuning::load_from(&path, "contiguous_parallel_tiled"),
+            Some(parallel)
+        );
+        assert_eq!(tuning::load_from(&path, "naive_fragmented"), None);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tuning_keeps_one_section_per_element_type() {
+        let path =
+            std::env::temp_dir().join(format!("matrix_tuning_types_{}.conf", std::process::id()));
+        let f32_tuning = Tuning {
+            block_size: 256,
+            loop_order: LoopOrder::Ikj,
+        };
+
+        assert_eq!(
+            tuning::section::<f64>("contiguous_tiled"),
+            "contiguous_tiled.f64"
+        );
+        tuning::save_to(
+            &path,
+            &tuning::section::<f32>("contiguous_tiled"),
+            f32_tuning,
+        )
+        .unwrap();
+
+        assert_eq!(
+            tuning::load_from(&path, &tuning::section::<f32>("contiguous_tiled")),
+            Some(f32_tuning)
+        );
+        assert_eq!(
+            tuning::load_from(&path, &tuning::section::<f64>("contiguous_tiled")),
+            None
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tuning_skips_invalid_sections() {
+        let path =
+            std::env::temp_dir().join(format!("matrix_tuning_bad_{}.conf", std::process::id()));
+        std::fs::write(
+            &path,
+            "[a]\nblock_size = 0\nloop_order = ijk\n[b]\nblock_size = 64\nloop_order = kji\n[c]\nloop_order = ikj\nblock_size = 32\n",
+        )
+        .unwrap();
+
+        assert_eq!(tuning::load_from(&path, "a"), None);
+        assert_eq!(tuning::load_from(&path, "b"), None);
+        assert_eq!(
+            tuning::load_from(&path, "c"),
+            Some(Tuning {
+                block_size: 32,
+                loop_order: LoopOrder::Ikj
+            })
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn sweep_measures_every_combination_fastest_first() {
+        let mut calls = 0;
+        let measurements = tuning::sweep(&[16, 32, 64], &LoopOrder::ALL, 2, |_, _| calls += 1);
+
+        // um aquecimento mais duas repetições por combinação
+        assert_eq!(calls, 3 * 2 * 3);
+        assert_eq!(measurements.len(), 6);
+        assert!(measurements
+            .windows(2)
+            .all(|w| w[0].seconds <= w[1].seconds));
+        assert_eq!("ikj".parse::<LoopOrder>(), Ok(LoopOrder::Ikj));
+        assert!("kij".parse::<LoopOrder>().is_err());
+    }
+
+    fn fake_sysfs(name: &str, caches: &[(&str, &str, &str)]) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+
+        for (index, (level, kind, size)) in caches.iter().enumerate() {
+            let dir = root.join(format!("cpu0/cache/index{}", index));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("level"), format!("{}\n", level)).unwrap();
+            std::fs::write(dir.join("type"), format!("{}\n", kind)).unwrap();
+            std::fs::write(dir.join("size"), format!("{}\n", size)).unwrap();
+            std::fs::write(dir.join("coherency_line_size"), "64\n").unwrap();
+        }
+        // diretórios que não são de CPU também ficam em /sys/devices/system/cpu
+        std::fs::create_dir_all(root.join("cpufreq")).unwrap();
+
+        root
+    }
+
+    #[test]
+    fn cache_topology_is_read_from_sysfs() {
+        let root = fake_sysfs(
+            "matrix_sysfs",
+            &[
+                ("1", "Data", "48K"),
+                ("1", "Instruction", "32K"),
+                ("2", "Unified", "2048K"),
+                ("3", "Unified", "300M"),
+            ],
+        );
+
+        assert_eq!(
+            CacheTopology::from_sysfs(&root),
+            Some(CacheTopology {
+                l1: 48 * 1024,
+                l2: 2048 * 1024,
+                l3: 300 * 1024 * 1024,
+                line_size: 64,
+            })
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn cache_topology_needs_l1_and_l2() {
+        let root = fake_sysfs("matrix_sysfs_l1", &[("1", "Data", "32K")]);
+        assert_eq!(CacheTopology::from_sysfs(&root), None);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let root = fake_sysfs(
+            "matrix_sysfs_no_l3",
+            &[("1", "Data", "32K"), ("2", "Unified", "512K")],
+        );
+        assert_eq!(CacheTopology::from_sysfs(&root).unwrap().l3, 512 * 1024);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            CacheTopology::from_sysfs(std::path::Path::new("/nonexistent/cpu")),
+            None
+        );
+    }
+
+    #[test]
+    fn three_tiles_fit_the_target_cache() {
+        let topology = CacheTopology {
+            l1: 48 * 1024,
+            l2: 2048 * 1024,
+            l3: 32 * 1024 * 1024,
+            line_size: 64,
+        };
+
+        for level in [CacheLevel::L1, CacheLevel::L2, CacheLevel::L3] {
+            for element_size in [4, 8, 16] {
+                let b = topology.tile_size(level, element_size);
+                let per_line = 64 / element_size;
+
+                assert!(3 * b * b * element_size <= topology.size(level));
+                assert!(3 * (b + per_line) * (b + per_line) * element_size > topology.size(level));
+                assert!(b.is_multiple_of(per_line));
+            }
+        }
+
+        // sqrt(2 MiB / 24) = 295, arredondado para linhas de 8 f64
+        let tiles = topology.tile_sizes::<f64>();
+        assert_eq!((tiles.multiply, tiles.add, tiles.subtract), (288, 40, 40));
+        assert_eq!(cache::FALLBACK.tile_sizes::<f64>().multiply, 104);
+
+        let tiny = CacheTopology { l1: 64, ..topology };
+        assert_eq!(tiny.tile_size(CacheLevel::L1, 8), 8);
+    }
+
+    fn naive_product(a: &[f64], b: &[f64], n: usize, m: usize, p: usize) -> Vec<f64> {
+        let mut c = vec![0.0; n * p];
+        for i in 0..n {
+            for k in 0..m {
+                for j in 0..p {
+                    c[i * p + j] += a[i * m + k] * b[k * p + j];
+                }
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn views_slice_rows_columns_blocks_and_transposes() {
+        // 0 1 2 3
+        // 4 5 6 7
+        // 8 9 10 11
+        let data: Vec<i64> = (0..12).collect();
+        let a = MatrixView::from_row_major(&data, 3, 4);
+
+        let block = a.submatrix(1..3, 1..4);
+        assert_eq!((block.rows(), block.cols()), (2, 3));
+        assert_eq!(block.to_vec(), vec![5, 6, 7, 9, 10, 11]);
+        assert_eq!(block.row_slice(1), Some(&data[9..12]));
+
+        assert_eq!(a.row(2).to_vec(), vec![8, 9, 10, 11]);
+        assert_eq!(a.col(1).to_vec(), vec![1, 5, 9]);
+
+        let transposed = a.transpose();
+        assert_eq!((transposed.rows(), transposed.cols()), (4, 3));
+        assert_eq!(transposed[(3, 1)], 7);
+        assert_eq!(transposed.row_slice(0), None);
+        assert_eq!(transposed.submatrix(2..4, 0..2).to_vec(), vec![2, 6, 3, 7]);
+        assert_eq!(a.submatrix(1..1, 0..4).to_vec(), Vec::<i64>::new());
+
+        assert_eq!(
+            MatrixView::new(&data, 3, 4, 5, 1).err(),
+            Some(MatrixError::InvalidDataLength {
+                expected: 14,
+                actual: 12
+            })
+        );
+    }
+
+    #[test]
+    fn mutable_views_only_touch_their_block() {
+        let mut data = vec![0i64; 12];
+        let mut a = MatrixViewMut::from_row_major(&mut data, 3, 4);
+
+        a.submatrix_mut(1..3, 2..4).fill(1);
+        a.col_mut(0)[(2, 0)] = 5;
+        a.transpose_mut()[(1, 0)] = 7;
+
+        assert_eq!(data, vec![0, 7, 0, 0, 0, 0, 1, 1, 5, 0, 1, 1]);
+    }
+
+    #[test]
+    fn view_kernels_read_blocks_and_transposes_in_place() {
+        let n = 12;
+        let a = sequence(n * n, 30.0);
+        let b: Vec<f64> = sequence(n * n, 10.0).iter().map(|x| x * 0.1).collect();
+        let a_view = MatrixView::from_row_major(&a, n, n);
+        let b_view = MatrixView::from_row_major(&b, n, n);
+
+        // bloco 5x7 de A vezes Bᵀ restrito a 7x4, pelo gemm e pelo loop com strides
+        let lhs = a_view.submatrix(2..7, 3..10);
+        for rhs in [
+            b_view.submatrix(1..8, 4..8),
+            b_view.transpose().submatrix(1..8, 4..8),
+        ] {
+            let mut c = vec![0.0; 5 * 4];
+            view::multiply_add(&lhs, &rhs, &mut MatrixViewMut::from_row_major(&mut c, 5, 4))
+                .unwrap();
+
+            let expected = naive_product(&lhs.to_vec(), &rhs.to_vec(), 5, 7, 4);
+            for (x, y) in c.iter().zip(&expected) {
+                assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+            }
+        }
+
+        let mut sum = vec![0.0; 5 * 7];
+        let mut out = MatrixViewMut::from_row_major(&mut sum, 5, 7);
+        view::add(&lhs, &b_view.transpose().submatrix(0..5, 0..7), &mut out).unwrap();
+        assert_eq!(out[(1, 1)], a[3 * n + 4] + b[n + 1]);
+        assert_eq!(out[(4, 2)], a[6 * n + 5] + b[2 * n + 4]);
+
+        assert_eq!(
+            view::add(&lhs, &a_view, &mut out),
+            Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (5, 7),
+                rhs: (12, 12)
+            })
+        );
+        assert!(view::multiply_add(&lhs, &lhs, &mut out).is_err());
+    }
+
+    /// `C += A * B` dividindo C em quadrantes, só com views.
+    fn multiply_by_quadrants(a: &MatrixView<f64>, b: &MatrixView<f64>, c: &mut MatrixViewMut<f64>) {
+        let (n, m, p) = (a.rows(), a.cols(), b.cols());
+        if n <= 4 || p <= 4 {
+            view::multiply_add(a, b, c).unwrap();
+            return;
+        }
+
+        let (hn, hp) = (n / 2, p / 2);
+        for (rows, cols) in [
+            (0..hn, 0..hp),
+            (0..hn, hp..p),
+            (hn..n, 0..hp),
+            (hn..n, hp..p),
+        ] {
+            let a_rows = a.submatrix(rows.clone(), 0..m);
+            let b_cols = b.submatrix(0..m, cols.clone());
+            multiply_by_quadrants(&a_rows, &b_cols, &mut c.submatrix_mut(rows, cols));
+        }
+    }
+
+    #[test]
+    fn block_recursion_over_views_needs_no_copies() {
+        let (n, m, p) = (19, 11, 23);
+        let a = sequence(n * m, 3.0);
+        let b = sequence(m * p, 1.0);
+        let mut c = vec![0.0; n * p];
+
+        multiply_by_quadrants(
+            &MatrixView::from_row_major(&a, n, m),
+            &MatrixView::from_row_major(&b, m, p),
+            &mut MatrixViewMut::from_row_major(&mut c, n, p),
+        );
+
+        let expected = naive_product(&a, &b, n, m, p);
+        for (x, y) in c.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+        }
+    }
+
+    /// 4x5 com uma linha vazia e uma coluna vazia.
+    fn sparse_example() -> Vec<f64> {
+        vec![
+            1.0, 0.0, 0.0, 2.0, 0.0, //
+            0.0, 0.0, 0.0, 0.0, 0.0, //
+            0.0, 3.0, 0.0, 0.0, 4.0, //
+            5.0, 0.0, 0.0, 6.0, 0.0,
+        ]
+    }
+
+    #[test]
+    fn sparse_formats_round_trip_through_dense() {
+        let dense = sparse_example();
+
+        let csr = CsrMatrix::from_dense(&dense, 4, 5);
+        assert_eq!(csr.row_offsets(), [0, 2, 2, 4, 6]);
+        assert_eq!(csr.col_indices(), [0, 3, 1, 4, 0, 3]);
+        assert_eq!(csr.get(2, 4), 4.0);
+        assert_eq!(csr.get(1, 1), 0.0);
+        assert_eq!(csr.to_dense(), dense);
+
+        let csc = csr.to_csc();
+        assert_eq!(csc.col_offsets(), [0, 2, 3, 3, 5, 6]);
+        assert_eq!(csc.row_indices(), [0, 3, 2, 0, 3, 2]);
+        assert_eq!(csc.to_dense(), dense);
+        assert_eq!(csc.to_csr(), csr);
+        assert_eq!(CscMatrix::from_dense(&dense, 4, 5), csc);
+
+        assert_eq!(csr.to_coo().to_csr(), csr);
+        assert_eq!(CooMatrix::from_dense(&dense, 4, 5).to_csc(), csc);
+
+        let transposed = csr.transpose();
+        assert_eq!((transposed.rows(), transposed.cols()), (5, 4));
+        for i in 0..4 {
+            for j in 0..5 {
+                assert_eq!(transposed.get(j, i), dense[i * 5 + j]);
+            }
+        }
+    }
+
+    #[test]
+    fn coo_to_csr_sorts_columns_and_sums_duplicates() {
+        let coo = CooMatrix::try_from_triplets(
+            3,
+            3,
+            &[
+                (2, 2, 1.0),
+                (0, 1, 2.0),
+                (2, 0, 3.0),
+                (0, 1, 4.0),
+                (1, 1, 0.0),
+            ],
+        )
+        .unwrap();
+        assert_eq!((coo.rows(), coo.cols(), coo.nnz()), (3, 3, 5));
+        assert_eq!(coo.row_indices(), [2, 0, 2, 0, 1]);
+        assert_eq!(coo.col_indices(), [2, 1, 0, 1, 1]);
+        let csr = coo.to_csr();
+
+        assert_eq!(csr.row_offsets(), [0, 1, 2, 4]);
+        assert_eq!(csr.col_indices(), [1, 1, 0, 2]);
+        assert_eq!(csr.values(), [6.0, 0.0, 3.0, 1.0]);
+        assert_eq!(coo.to_dense(), csr.to_dense());
+
+        assert_eq!(
+            CooMatrix::try_from_triplets(2, 2, &[(0, 2, 1.0)]),
+            Err(MatrixError::InvalidSparseStructure {
+                reason: "entry outside the matrix"
+            })
+        );
+    }
+
+    #[test]
+    fn csr_try_new_rejects_broken_structure() {
+        assert!(CsrMatrix::try_new(2, 3, vec![0, 1, 2], vec![2, 0], vec![1.0, 2.0]).is_ok());
+
+        for (offsets, cols) in [
+            (vec![0, 1], vec![2, 0]),
+            (vec![1, 1, 2], vec![2, 0]),
+            (vec![0, 2, 1], vec![2, 0]),
+            (vec![0, 1, 2], vec![3, 0]),
+            (vec![0, 2, 2], vec![2, 0]),
+            (vec![0, 2, 2], vec![1, 1]),
+            (vec![0, 3, 2], vec![2, 0]),
+        ] {
+            assert!(matches!(
+                CsrMatrix::try_new(2, 3, offsets, cols, vec![1.0, 2.0]),
+                Err(MatrixError::InvalidSparseStructure { .. })
+            ));
+        }
+        // `rows + 1` não cabe em usize: erro, não overflow
+        assert!(matches!(
+            CsrMatrix::<f64>::try_new(usize::MAX, 3, vec![0], Vec::new(), Vec::new()),
+            Err(MatrixError::InvalidSparseStructure { .. })
+        ));
+    }
+
+    #[test]
+    fn sparse_products_match_dense() {
+        let dense = sparse_example();
+        let csr = CsrMatrix::from_dense(&dense, 4, 5);
+        let x = sequence(5, 1.0);
+
+        let expected: Vec<f64> = (0..4)
+            .map(|i| (0..5).map(|j| dense[i * 5 + j] * x[j]).sum())
+            .collect();
+        assert_eq!(csr.spmv(&x).unwrap(), expected);
+        assert_eq!(csr.to_csc().spmv(&x).unwrap(), expected);
+        assert!(csr.spmv(&x[..4]).is_err());
+
+        let b = sequence(5 * 3, 2.0);
+        let product = csr
+            .multiply_dense(&MatrixView::from_row_major(&b, 5, 3))
+            .unwrap();
+        assert_eq!(product, naive_product(&dense, &b, 4, 5, 3));
+
+        // B transposta: as linhas não são contíguas
+        let bt = sequence(3 * 5, 2.0);
+        let product = csr
+            .multiply_dense(&MatrixView::from_row_major(&bt, 3, 5).transpose())
+            .unwrap();
+        let bt_transposed: Vec<f64> = (0..5)
+            .flat_map(|k| (0..3).map(move |j| (k, j)))
+            .map(|(k, j)| bt[j * 5 + k])
+            .collect();
+        assert_eq!(product, naive_product(&dense, &bt_transposed, 4, 5, 3));
+
+        assert_eq!(
+            csr.multiply_dense(&MatrixView::from_row_major(&b, 3, 5)),
+            Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (4, 5),
+                rhs: (3, 5)
+            })
+        );
+    }
+
+    #[test]
+    fn csr_add_merges_rows() {
+        let a = CsrMatrix::from_dense(&sparse_example(), 4, 5);
+        let b = CsrMatrix::identity(4);
+        assert!(a.add(&b).is_err());
+
+        let c = CsrMatrix::from_dense(
+            &[
+                0.0, 1.0, 0.0, -2.0, 0.0, //
+                0.0, 0.0, 7.0, 0.0, 0.0, //
+                0.0, 0.0, 0.0, 0.0, 0.0, //
+                1.0, 0.0, 0.0, 0.0, 9.0,
+            ],
+            4,
+            5,
+        );
+        let sum = a.add(&c).unwrap();
+        let expected: Vec<f64> = a
+            .to_dense()
+            .iter()
+            .zip(c.to_dense())
+            .map(|(x, y)| x + y)
+            .collect();
+
+        assert_eq!(sum.to_dense(), expected);
+        // o 2 - 2 da linha 0 continua como zero explícito
+        assert_eq!(sum.row(0).0, &[0, 1, 3]);
+        assert_eq!(sum.nnz(), 9);
+    }
+
+    fn parse_error<T: std::fmt::Debug>(result: Result<T, FormatError>) -> (usize, String) {
+        match result {
+            Err(FormatError::Parse { line, message }) => (line, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn market_reads_coordinate_general_and_symmetric() {
+        let general = "%%MatrixMarket matrix coordinate real general
+% comentário
+
+3 4 4
+1 1 1.5
+3 2 -2e1
+1 4 3
+1 1 0.5
+";
+        let (rows, cols, data) = market::read_dense::<f64, _>(general.as_bytes()).unwrap();
+        assert_eq!((rows, cols), (3, 4));
+        assert_eq!(
+            data,
+            vec![2.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0, 0.0, -20.0, 0.0, 0.0]
+        );
+
+        let symmetric = "%%MatrixMarket matrix coordinate integer symmetric
+3 3 3
+1 1 4
+3 1 -1
+3 2 7
+";
+        let csr = market::read_csr::<i64, _>(symmetric.as_bytes()).unwrap();
+        assert_eq!(csr.to_dense(), vec![4, 0, -1, 0, 0, 7, -1, 7, 0]);
+
+        let pattern = "%%MatrixMarket matrix coordinate pattern general
+2 3 2
+1 3
+2 1
+";
+        let coo = market::read_coo::<f64, _>(pattern.as_bytes()).unwrap();
+        assert_eq!(coo.to_dense(), vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn market_reads_array_column_major() {
+        let general = "%%MatrixMarket matrix array real general
+2 3
+1
+4
+2
+5
+3
+6
+";
+        let (_, _, data) = market::read_dense::<f64, _>(general.as_bytes()).unwrap();
+        assert_eq!(data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        // triângulo de baixo por coluna: (1,1) (2,1) (3,1) (2,2) (3,2) (3,3)
+        let symmetric = "%%MatrixMarket matrix array real symmetric
+3 3
+1
+2
+3
+4
+5
+6
+";
+        let (_, _, data) = market::read_dense::<f64, _>(symmetric.as_bytes()).unwrap();
+        assert_eq!(data, vec![1.0, 2.0, 3.0, 2.0, 4.0, 5.0, 3.0, 5.0, 6.0]);
+
+        // zeros do array não viram entradas
+        assert_eq!(
+            market::read_coo::<f64, _>(general.replace("\n4\n", "\n0\n").as_bytes())
+                .unwrap()
+                .nnz(),
+            5
+        );
+    }
+
+    #[test]
+    fn market_errors_report_the_line() {
+        let read = |text: &str| market::read_dense::<f64, _>(text.as_bytes());
+
+        assert_eq!(
+            parse_error(read(
+                "%%MatrixMarket matrix coordinate complex general\n1 1 0\n"
+            )),
+            (1, "unsupported field 'complex'".to_string())
+        );
+        assert_eq!(parse_error(read("")).0, 1);
+        assert_eq!(
+            parse_error(read(
+                "%%MatrixMarket matrix coordinate real general\n% x\n2 2\n"
+            )),
+            (3, "expected 'rows cols entries'".to_string())
+        );
+
+        let header = "%%MatrixMarket matrix coordinate real general\n2 2 2\n";
+        assert_eq!(
+            parse_error(read(&format!("{}1 1 1.0\n\n2 x 1.0\n", header))),
+            (5, "invalid column 'x'".to_string())
+        );
+        assert_eq!(
+            parse_error(read(&format!("{}1 1 1.0\n3 1 1.0\n", header))),
+            (4, "entry (3, 1) outside a 2x2 matrix".to_string())
+        );
+        assert_eq!(
+            parse_error(read(&format!("{}1 1 abc\n", header))),
+            (3, "invalid value 'abc'".to_string())
+        );
+        assert_eq!(
+            parse_error(read(&format!("{}1 1 1.0\n", header))),
+            (3, "expected 2 entries, found 1".to_string())
+        );
+        assert_eq!(
+            parse_error(read(&format!("{}1 1 1.0\n2 2 1.0\n1 2 1.0\n", header))),
+            (5, "more than the 2 declared entries".to_string())
+        );
+        assert_eq!(
+            parse_error(read("%%MatrixMarket matrix array real symmetric\n2 3\n1\n")).0,
+            2
+        );
+    }
+
+    #[test]
+    fn market_rejects_huge_sizes() {
+        let coo = |text: &str| market::read_coo::<f64, _>(text.as_bytes());
+        let general = "%%MatrixMarket matrix coordinate real general\n";
+        let symmetric = "%%MatrixMarket matrix coordinate real symmetric\n";
+
+        assert_eq!(
+            parse_error(coo(
+                "%%MatrixMarket matrix array real general\n4294967296 4294967296\n"
+            )),
+            (2, "a 4294967296x4294967296 matrix is too large".to_string())
+        );
+        // o número de entradas não é reservado de uma vez
+        for text in [
+            format!("{}3 3 9223372036854775808\n", symmetric),
+            format!("{}3 3 18446744073709551615\n", general),
+            format!("{}3 3 100000000000\n1 1 1.0\n", general),
+        ] {
+            assert!(parse_error(coo(&text)).1.starts_with("expected "));
+        }
+
+        let dense = market::read_dense::<f64, _>(
+            format!("{}4294967296 4294967296 0\n", general).as_bytes(),
+        );
+        assert_eq!(
+            parse_error(dense),
+            (
+                2,
+                "a 4294967296x4294967296 matrix is too large to store dense".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn market_write_round_trips() {
+        let data: Vec<f64> = vec![
+            1.0, 0.5, 0.0, //
+            0.5, 0.0, -3e-300, //
+            0.0, -3e-300, 1e300,
+        ];
+
+        for format in [MarketHeader::coordinate(), MarketHeader::array()] {
+            for symmetry in [MarketSymmetry::General, MarketSymmetry::Symmetric] {
+                let header = MarketHeader { symmetry, ..format };
+                let mut file = Vec::new();
+                market::write_dense(&mut file, header, 3, 3, &data).unwrap();
+
+                let text = String::from_utf8(file).unwrap();
+                assert!(text.starts_with(&header.to_string()));
+                let (rows, cols, read) = market::read_dense::<f64, _>(text.as_bytes()).unwrap();
+                assert_eq!((rows, cols, read), (3, 3, data.clone()), "{}", text);
+            }
+        }
+
+        // só o triângulo de baixo no arquivo simétrico
+        let mut file = Vec::new();
+        let header = MarketHeader {
+            symmetry: MarketSymmetry::Symmetric,
+            ..MarketHeader::coordinate()
+        };
+        market::write_dense(&mut file, header, 3, 3, &data).unwrap();
+        assert_eq!(
+            String::from_utf8(file).unwrap().lines().nth(1),
+            Some("3 3 4")
+        );
+
+        let mut file = Vec::new();
+        let header = MarketHeader {
+            field: MarketField::Pattern,
+            ..MarketHeader::coordinate()
+        };
+        market::write_csr(&mut file, header, &CsrMatrix::<i64>::identity(2)).unwrap();
+        assert_eq!(
+            String::from_utf8(file).unwrap(),
+            "%%MatrixMarket matrix coordinate pattern general\n2 2 2\n1 1\n2 2\n"
+        );
+
+        let not_symmetric = [1.0, 2.0, 3.0, 4.0];
+        let header = MarketHeader {
+            symmetry: MarketSymmetry::Symmetric,
+            ..MarketHeader::array()
+        };
+        assert!(matches!(
+            market::write_dense(Vec::new(), header, 2, 2, &not_symmetric),
+            Err(FormatError::Matrix(MatrixError::NotSymmetric))
+        ));
+        let header = MarketHeader {
+            field: MarketField::Pattern,
+            ..MarketHeader::array()
+        };
+        assert!(matches!(
+            market::write_dense(Vec::new(), header, 2, 2, &not_symmetric),
+            Err(FormatError::Unsupported(_))
+        ));
+    }
+
+    // fixtures escritos como o `numpy.save`/`numpy.savez` escrevem; o
+    // comentário de cada um diz o array de origem
+
+    #[test]
+    fn npy_reads_numpy_fixtures() {
+        // np.arange(6.0).reshape(2, 3)
+        let (rows, cols, data) =
+            npy::read::<f64, _>(&include_bytes!("fixtures/arange_2x3.npy")[..]).unwrap();
+        assert_eq!((rows, cols), (2, 3));
+        assert_eq!(data, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        // np.asfortranarray(np.arange(6, dtype=np.float32).reshape(3, 2))
+        let fortran = &include_bytes!("fixtures/fortran_3x2_f32.npy")[..];
+        let (rows, cols, data) = npy::read::<f32, _>(fortran).unwrap();
+        assert_eq!((rows, cols), (3, 2));
+        assert_eq!(data, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        // f32 do arquivo lido como f64
+        assert_eq!(npy::read::<f64, _>(fortran).unwrap().2[5], 5.0);
+
+        // np.lib.format.write_array(f, np.array([1.5, -2.0, 1e-300, 4.0]),
+        //                           version=(2, 0))
+        let (rows, cols, data) =
+            npy::read::<f64, _>(&include_bytes!("fixtures/vector_v2.npy")[..]).unwrap();
+        assert_eq!((rows, cols), (4, 1));
+        assert_eq!(data, vec![1.5, -2.0, 1e-300, 4.0]);
+    }
+
+    #[test]
+    fn npy_write_matches_numpy_bytes() {
+        let mut file = Vec::new();
+        npy::write(&mut file, 2, 3, &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+        assert_eq!(file, include_bytes!("fixtures/arange_2x3.npy"));
+
+        let data: Vec<f32> = (0..12).map(|x| x as f32 / 3.0).collect();
+        let mut file = Vec::new();
+        npy::write(&mut file, 4, 3, &data).unwrap();
+        // os dados começam num múltiplo de 64
+        assert_eq!((file.len() - 12 * 4) % 64, 0);
+        assert_eq!(npy::read::<f32, _>(file.as_slice()).unwrap(), (4, 3, data));
+    }
+
+    #[test]
+    fn npy_rejects_bad_headers() {
+        let header = |dict: &str| {
+            let mut file = b"\x93NUMPY\x01\x00".to_vec();
+            file.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+            file.extend_from_slice(dict.as_bytes());
+            file.extend_from_slice(&[0; 64]);
+            match npy::read::<f64, _>(file.as_slice()) {
+                Err(FormatError::InvalidHeader(message)) => message,
+                other => panic!("expected an invalid header, got {:?}", other),
+            }
+        };
+
+        assert_eq!(
+            header("{'descr': '>f8', 'fortran_order': False, 'shape': (2,), }"),
+            "unsupported dtype '>f8', expected '<f8' or '<f4'"
+        );
+        assert_eq!(
+            header("{'descr': '<f8', 'shape': (2,), }"),
+            "missing 'fortran_order'"
+        );
+        assert_eq!(
+            header("{'descr': '<f8', 'fortran_order': False, 'shape': (2, 2, 2), }"),
+            "only 1-D and 2-D arrays are supported, got 3 dimensions"
+        );
+        assert!(matches!(
+            npy::read::<f64, _>(&b"PK\x03\x04 not npy"[..]),
+            Err(FormatError::InvalidHeader(_))
+        ));
+        // dados faltando
+        assert!(matches!(
+            npy::read::<f64, _>(&include_bytes!("fixtures/arange_2x3.npy")[..150]),
+            Err(FormatError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn npy_and_npz_reject_huge_sizes() {
+        let npy = |shape: &str| {
+            let dict = format!(
+                "{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}",
+                shape
+            );
+            let mut file = b"\x93NUMPY\x01\x00".to_vec();
+            file.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+            file.extend_from_slice(dict.as_bytes());
+            npy::read::<f64, _>(file.as_slice())
+        };
+        assert!(matches!(
+            npy("(4294967296, 4294967296)"),
+            Err(FormatError::InvalidHeader(_))
+        ));
+        // cabe na conta, mas o buffer não é alocado antes dos dados chegarem
+        assert!(matches!(
+            npy("(100000000000, 1000)"),
+            Err(FormatError::Io(_))
+        ));
+
+        let truncated = |zip: &[u8]| match npy::read_npz::<f64, _>(zip) {
+            Err(FormatError::InvalidHeader(message)) => {
+                assert_eq!(message, "truncated zip archive")
+            }
+            other => panic!("expected a truncated archive, got {:?}", other),
+        };
+        let mut file = Vec::new();
+        npy::write_npz(&mut file, &[("x", 1, 1, &[1.0][..])]).unwrap();
+        let central = file.windows(4).position(|w| w == b"PK\x01\x02").unwrap();
+        let end = file.windows(4).position(|w| w == b"PK\x05\x06").unwrap();
+
+        // tamanho zip64 de quase 2^64 bytes
+        let mut huge_size = file.clone();
+        huge_size[central + 20..central + 24].copy_from_slice(&u32::MAX.to_le_bytes());
+        huge_size[central + 30..central + 32].copy_from_slice(&12u16.to_le_bytes());
+        let mut extra = vec![1, 0, 8, 0];
+        extra.extend_from_slice(&(u64::MAX - 1).to_le_bytes());
+        let name_end = central + 46 + "x.npy".len();
+        huge_size.splice(name_end..name_end, extra);
+        truncated(&huge_size);
+
+        // registro zip64 do fim do diretório numa posição impossível
+        let mut huge_end = file[..end].to_vec();
+        huge_end.extend_from_slice(b"PK\x06\x07\0\0\0\0");
+        huge_end.extend_from_slice(&(u64::MAX - 2).to_le_bytes());
+        huge_end.extend_from_slice(&1u32.to_le_bytes());
+        huge_end.extend_from_slice(&file[end..]);
+        let at = huge_end.len() - 22;
+        huge_end[at + 10..at + 12].copy_from_slice(&u16::MAX.to_le_bytes());
+        truncated(&huge_end);
+    }
+
+    #[test]
+    fn npz_reads_savez_and_round_trips() {
+        // np.savez(f, a=np.eye(2), b=np.arange(3.0).reshape(1, 3))
+        let savez = &include_bytes!("fixtures/savez.npz")[..];
+        let arrays = npy::read_npz::<f64, _>(savez).unwrap();
+        assert_eq!(
+            arrays,
+            vec![
+                NpzArray {
+                    name: "a".to_string(),
+                    rows: 2,
+                    cols: 2,
+                    data: vec![1.0, 0.0, 0.0, 1.0]
+                },
+                NpzArray {
+                    name: "b".to_string(),
+                    rows: 1,
+                    cols: 3,
+                    data: vec![0.0, 1.0, 2.0]
+                },
+            ]
+        );
+
+        let x = sequence(6, 1.0);
+        let mut file = Vec::new();
+        npy::write_npz(&mut file, &[("x", 3, 2, &x[..]), ("y", 1, 1, &[7.0][..])]).unwrap();
+        let arrays = npy::read_npz::<f64, _>(file.as_slice()).unwrap();
+        assert_eq!(arrays.len(), 2);
+        assert_eq!((arrays[0].name.as_str(), arrays[0].rows), ("x", 3));
+        assert_eq!(arrays[0].data, x);
+        assert_eq!(arrays[1].data, vec![7.0]);
+
+        // um byte trocado nos dados de `a`
+        let mut corrupted = savez.to_vec();
+        let at = corrupted
+            .windows(6)
+            .position(|w| w == b"\x93NUMPY")
+            .unwrap()
+            + 130;
+        corrupted[at] ^= 1;
+        assert!(matches!(
+            npy::read_npz::<f64, _>(corrupted.as_slice()),
+            Err(FormatError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn csv_reads_both_locales() {
+        let text = "1.5,2,-3e2\n\n4,\"5.25\",6\n";
+        let table = csv::read::<f64, _>(text.as_bytes(), CsvFormat::default()).unwrap();
+        assert_eq!((table.header, table.rows, table.cols), (None, 2, 3));
+        assert_eq!(table.data, vec![1.5, 2.0, -300.0, 4.0, 5.25, 6.0]);
+
+        // linha do CSV gerado pelo `analysis`, com cabeçalho
+        let perf = "Task Clock (msec);CPUs Utilized;Core Cache Misses\r\n\
+                    1.332,12;0,998;7.159.331\r\n\
+                    834,33;\"0,998\";6.069.890\r\n";
+        let format = CsvFormat {
+            has_header: true,
+            ..CsvFormat::decimal_comma()
+        };
+        let table = csv::read::<f64, _>(perf.as_bytes(), format).unwrap();
+        assert_eq!(table.header.unwrap()[1], "CPUs Utilized");
+        assert_eq!((table.rows, table.cols), (2, 3));
+        assert_eq!(
+            table.data,
+            vec![1332.12, 0.998, 7159331.0, 834.33, 0.998, 6069890.0]
+        );
+
+        let table = csv::read::<i64, _>(
+            "1\t-2\n3\t4\n".as_bytes(),
+            CsvFormat {
+                delimiter: '\t',
+                ..CsvFormat::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(table.data, vec![1, -2, 3, 4]);
+    }
+
+    #[test]
+    fn csv_errors_point_at_the_cell() {
+        let read = |text: &str, format| match csv::read::<f64, _>(text.as_bytes(), format) {
+            Err(FormatError::Cell { row, col, message }) => (row, col, message),
+            other => panic!("expected a cell error, got {:?}", other),
+        };
+
+        assert_eq!(
+            read("1,2,3\n4,x5,6\n", CsvFormat::default()),
+            (2, 2, "invalid number 'x5'".to_string())
+        );
+        assert_eq!(
+            read(
+                "a;b\n1;2\n\n3; \n",
+                CsvFormat {
+                    has_header: true,
+                    ..CsvFormat::decimal_comma()
+                }
+            ),
+            (4, 2, "empty cell".to_string())
+        );
+        assert_eq!(
+            read("1,2,3\n4,5\n", CsvFormat::default()),
+            (2, 3, "expected 3 cells, found 2".to_string())
+        );
+        assert_eq!(
+            read("1,2\n3,\"4\n", CsvFormat::default()),
+            (2, 1, "unterminated quote".to_string())
+        );
+        // o ponto decimal vira separador de milhar na vírgula decimal
+        assert_eq!(
+            read("1,5;2e\n", CsvFormat::decimal_comma()),
+            (1, 2, "invalid number '2e'".to_string())
+        );
+
+        assert!(matches!(
+            csv::read::<f64, _>(
+                "1".as_bytes(),
+                CsvFormat {
+                    delimiter: '.',
+                    ..CsvFormat::default()
+                }
+            ),
+            Err(FormatError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn csv_write_round_trips() {
+        let data = vec![1.5, -0.25, 1e-7, 3.0, 1234.5, 0.1];
+        for format in [
+            CsvFormat::default(),
+            CsvFormat::decimal_comma(),
+            CsvFormat {
+                has_header: true,
+                ..CsvFormat::decimal_comma()
+            },
+        ] {
+            let mut file = Vec::new();
+            csv::write(&mut file, format, 2, 3, &data).unwrap();
+            let table = csv::read::<f64, _>(file.as_slice(), format).unwrap();
+            assert_eq!((table.rows, table.cols), (2, 3));
+            assert_eq!(table.data, data);
+        }
+
+        let mut file = Vec::new();
+        let format = CsvFormat {
+            has_header: true,
+            ..CsvFormat::decimal_comma()
+        };
+        csv::write(&mut file, format, 2, 3, &data).unwrap();
+        assert_eq!(
+            String::from_utf8(file).unwrap(),
+            "0;1;2\n1,5;-0,25;0,0000001\n3;1234,5;0,1\n"
+        );
+    }
+
+    #[test]
+    fn binary_round_trips_both_layouts() {
+        let data: Vec<i32> = vec![1, 2, 3, 4, 5, 6];
+        let mut file = Vec::new();
+        binary::write(&mut file, Layout::RowMajor, 2, 3, &data).unwrap();
+        assert_eq!(file.len(), binary::HEADER_LEN + 6 * 4);
+        assert_eq!(&file[..8], b"MATRIXB\0");
+        assert_eq!(
+            BinaryHeader::parse(&file).unwrap(),
+            BinaryHeader {
+                dtype: Dtype::I32,
+                layout: Layout::RowMajor,
+                rows: 2,
+                cols: 3,
+                checksum: crc32fast::hash(&file[binary::HEADER_LEN..]),
+            }
+        );
+        assert_eq!(
+            binary::read::<i32, _>(file.as_slice()).unwrap(),
+            (2, 3, data.clone())
+        );
+
+        // os mesmos bytes lidos por coluna: [[1, 3, 5], [2, 4, 6]]
+        let mut file = Vec::new();
+        binary::write(&mut file, Layout::ColumnMajor, 2, 3, &data).unwrap();
+        assert_eq!(
+            binary::read::<i32, _>(file.as_slice()).unwrap(),
+            (2, 3, vec![1, 3, 5, 2, 4, 6])
+        );
+    }
+
+    #[test]
+    fn binary_rejects_bad_files() {
+        let mut file = Vec::new();
+        binary::write(&mut file, Layout::RowMajor, 2, 2, &[1.0f64, 2.0, 3.0, 4.0]).unwrap();
+
+        assert!(matches!(
+            binary::read::<f32, _>(file.as_slice()),
+            Err(FormatError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            binary::read::<f64, _>(&file[..file.len() - 1]),
+            Err(FormatError::Io(_))
+        ));
+
+        let mut corrupted = file.clone();
+        corrupted[binary::HEADER_LEN] ^= 1;
+        assert!(matches!(
+            binary::read::<f64, _>(corrupted.as_slice()),
+            Err(FormatError::ChecksumMismatch { .. })
+        ));
+
+        for (at, value) in [(0, b'X'), (8, 2), (10, 0), (11, 7), (32, 0)] {
+            let mut bad = file.clone();
+            bad[at] = value;
+            assert!(matches!(
+                binary::read::<f64, _>(bad.as_slice()),
+                Err(FormatError::InvalidHeader(_))
+            ));
+        }
+
+        assert!(matches!(
+            binary::write(Vec::new(), Layout::RowMajor, 2, 2, &[1.0f64]),
+            Err(FormatError::Matrix(MatrixError::InvalidDataLength { .. }))
+        ));
+
+        // cabeçalho de 2^30 x 2^30 com só 4 elementos depois dele
+        let mut huge = file.clone();
+        huge[16..24].copy_from_slice(&(1u64 << 30).to_le_bytes());
+        huge[24..32].copy_from_slice(&(1u64 << 30).to_le_bytes());
+        assert!(matches!(
+            binary::read::<f64, _>(huge.as_slice()),
+            Err(FormatError::Io(_) | FormatError::InvalidHeader(_))
+        ));
+        let mut overflowing = file.clone();
+        overflowing[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        overflowing[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            binary::read::<f64, _>(overflowing.as_slice()),
+            Err(FormatError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn mapped_matrix_views_the_file() {
+        let path = std::env::temp_dir().join(format!("matrix_mapped_{}.bin", std::process::id()));
+        let data = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let mut file = Vec::new();
+        binary::write(&mut file, Layout::ColumnMajor, 3, 2, &data).unwrap();
+        std::fs::write(&path, &file).unwrap();
+
+        let mapped = MappedMatrix::<f32>::open(&path).unwrap();
+        let view = mapped.view();
+        assert_eq!((view.rows(), view.cols(), view.col_stride()), (3, 2, 3));
+        assert_eq!(view.to_vec(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        assert_eq!(mapped.as_slice(), &data);
+        drop(mapped);
+
+        assert!(matches!(
+            MappedMatrix::<f64>::open(&path),
+            Err(FormatError::InvalidHeader(_))
+        ));
+
+        file[binary::HEADER_LEN + 3] ^= 0x40;
+        std::fs::write(&path, &file).unwrap();
+        assert!(matches!(
+            MappedMatrix::<f32>::open(&path),
+            Err(FormatError::ChecksumMismatch { .. })
+        ));
+        let unverified = MappedMatrix::<f32>::open_unverified(&path).unwrap();
+        assert!(unverified.verify().is_err());
+        drop(unverified);
+
+        std::fs::write(&path, &file[..binary::HEADER_LEN + 8]).unwrap();
+        let truncated = MappedMatrix::<f32>::open_unverified(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(truncated, Err(FormatError::InvalidHeader(_))));
+    }
+
+    #[test]
+    fn strassen_recursion_matches_its_kernel() {
+        fn kernel(a: &[i64], b: &[i64], s: usize) -> Vec<i64> {
+            let mut c = vec![0; s * s];
+            for i in 0..s {
+                for k in 0..s {
+                    for j in 0..s {
+                        c[i * s + j] += a[i * s + k] * b[k * s + j];
+                    }
+                }
+            }
+            c
+        }
+
+        let (rows, inner, cols) = (13, 9, 11);
+        let a: Vec<i64> = (0..rows * inner).map(|x| (x % 7) as i64 - 3).collect();
+        let b: Vec<i64> = (0..inner * cols).map(|x| (x % 5) as i64 - 2).collect();
+
+        // 13 vira 16 = 2 * 2^3, então a recursão desce três níveis
+        let s = strassen::padded_size(rows.max(inner).max(cols), 3);
+        assert_eq!(s, 16);
+        let a = strassen::pad(&a, rows, inner, s);
+        let b = strassen::pad(&b, inner, cols, s);
+        let padded = strassen::multiply(&a, &b, s, 3, &kernel);
+        assert_eq!(padded, kernel(&a, &b, s));
+        assert_eq!(strassen::unpad(&padded, s, rows, cols).len(), rows * cols);
+    }
+}
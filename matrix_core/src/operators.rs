@@ -0,0 +1,262 @@
+// Operadores do `std::ops` para os `Matrix` contíguos (`data` row-major,
+// `rows`, `cols`). Os quatro crates contíguos têm os mesmos operadores, então
+// eles são gerados aqui por uma macro em vez de copiados:
+//
+//     matrix_core::impl_contiguous_operators!(Matrix);
+//
+// A macro espera, no crate que a chama, `Matrix<T>` com `MatrixOps`,
+// `apply_function`, `apply_function_mut`, `add_assign` e `sub_assign`. Todos
+// os operadores passam por esses métodos, então cada crate usa o próprio
+// kernel. Quando o lado esquerdo é passado por valor, o resultado de `+`,
+// `-`, `* escalar` e `-a` é escrito no buffer dele, sem alocar. Dimensões
+// incompatíveis causam panic, como nos métodos sem `try_`.
+
+/// Implements `+`, `-`, `*` (matrix and scalar), unary `-`, the assign
+/// operators and `a[(i, j)]` for a contiguous row-major `Matrix<T>`.
+#[macro_export]
+macro_rules! impl_contiguous_operators {
+    ($matrix:ident) => {
+        impl<T: $crate::Scalar> ::std::ops::Add<&$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn add(self, rhs: &$matrix<T>) -> $matrix<T> {
+                $crate::MatrixOps::add(self, rhs)
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Add<&$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn add(mut self, rhs: &$matrix<T>) -> $matrix<T> {
+                self.add_assign(rhs);
+                self
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Add<$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn add(self, mut rhs: $matrix<T>) -> $matrix<T> {
+                rhs.add_assign(self);
+                rhs
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Add<$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn add(self, rhs: $matrix<T>) -> $matrix<T> {
+                self + &rhs
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Sub<&$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn sub(self, rhs: &$matrix<T>) -> $matrix<T> {
+                $crate::MatrixOps::subtract(self, rhs)
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Sub<&$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn sub(mut self, rhs: &$matrix<T>) -> $matrix<T> {
+                self.sub_assign(rhs);
+                self
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Sub<$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn sub(self, rhs: $matrix<T>) -> $matrix<T> {
+                self - &rhs
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Sub<$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn sub(self, rhs: $matrix<T>) -> $matrix<T> {
+                self - &rhs
+            }
+        }
+
+        /// Matrix product.
+        impl<T: $crate::Scalar> ::std::ops::Mul<&$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(self, rhs: &$matrix<T>) -> $matrix<T> {
+                $crate::MatrixOps::multiply(self, rhs)
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Mul<&$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(self, rhs: &$matrix<T>) -> $matrix<T> {
+                &self * rhs
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Mul<$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(self, rhs: $matrix<T>) -> $matrix<T> {
+                self * &rhs
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Mul<$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(self, rhs: $matrix<T>) -> $matrix<T> {
+                &self * &rhs
+            }
+        }
+
+        /// Every element times a scalar.
+        impl<T: $crate::Scalar> ::std::ops::Mul<T> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(self, rhs: T) -> $matrix<T> {
+                self.apply_function(&|x| x * rhs)
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::Mul<T> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(mut self, rhs: T) -> $matrix<T> {
+                self *= rhs;
+                self
+            }
+        }
+
+        $crate::__impl_scalar_lhs_mul!($matrix; f32, f64, i32, i64, u32, u64);
+
+        impl<T> ::std::ops::Neg for &$matrix<T>
+        where
+            T: $crate::Scalar + ::std::ops::Neg<Output = T>,
+        {
+            type Output = $matrix<T>;
+
+            fn neg(self) -> $matrix<T> {
+                self.apply_function(&|x| -x)
+            }
+        }
+
+        impl<T> ::std::ops::Neg for $matrix<T>
+        where
+            T: $crate::Scalar + ::std::ops::Neg<Output = T>,
+        {
+            type Output = $matrix<T>;
+
+            fn neg(mut self) -> $matrix<T> {
+                self.apply_function_mut(&|x| -x);
+                self
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::AddAssign<&$matrix<T>> for $matrix<T> {
+            fn add_assign(&mut self, rhs: &$matrix<T>) {
+                $matrix::add_assign(self, rhs);
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::AddAssign<$matrix<T>> for $matrix<T> {
+            fn add_assign(&mut self, rhs: $matrix<T>) {
+                $matrix::add_assign(self, &rhs);
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::SubAssign<&$matrix<T>> for $matrix<T> {
+            fn sub_assign(&mut self, rhs: &$matrix<T>) {
+                $matrix::sub_assign(self, rhs);
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::SubAssign<$matrix<T>> for $matrix<T> {
+            fn sub_assign(&mut self, rhs: $matrix<T>) {
+                $matrix::sub_assign(self, &rhs);
+            }
+        }
+
+        /// `a *= &b` is `a = &a * &b`; the product needs a new buffer.
+        impl<T: $crate::Scalar> ::std::ops::MulAssign<&$matrix<T>> for $matrix<T> {
+            fn mul_assign(&mut self, rhs: &$matrix<T>) {
+                *self = &*self * rhs;
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::MulAssign<$matrix<T>> for $matrix<T> {
+            fn mul_assign(&mut self, rhs: $matrix<T>) {
+                *self *= &rhs;
+            }
+        }
+
+        impl<T: $crate::Scalar> ::std::ops::MulAssign<T> for $matrix<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                self.apply_function_mut(&|x| x * rhs);
+            }
+        }
+
+        /// `a[(i, j)]`, checking both indices (a bad `j` would otherwise
+        /// land on another row).
+        impl<T> ::std::ops::Index<(usize, usize)> for $matrix<T> {
+            type Output = T;
+
+            fn index(&self, (i, j): (usize, usize)) -> &T {
+                assert!(
+                    i < self.rows && j < self.cols,
+                    "Index ({}, {}) out of bounds for a {}x{} matrix",
+                    i,
+                    j,
+                    self.rows,
+                    self.cols
+                );
+                &self.data[i * self.cols + j]
+            }
+        }
+
+        impl<T> ::std::ops::IndexMut<(usize, usize)> for $matrix<T> {
+            fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+                assert!(
+                    i < self.rows && j < self.cols,
+                    "Index ({}, {}) out of bounds for a {}x{} matrix",
+                    i,
+                    j,
+                    self.rows,
+                    self.cols
+                );
+                &mut self.data[i * self.cols + j]
+            }
+        }
+    };
+}
+
+// escalar à esquerda (`2.0 * &a`) só dá para implementar tipo a tipo
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_scalar_lhs_mul {
+    ($matrix:ident; $($t:ty),*) => {
+        $(
+            impl ::std::ops::Mul<&$matrix<$t>> for $t {
+                type Output = $matrix<$t>;
+
+                fn mul(self, rhs: &$matrix<$t>) -> $matrix<$t> {
+                    rhs * self
+                }
+            }
+
+            impl ::std::ops::Mul<$matrix<$t>> for $t {
+                type Output = $matrix<$t>;
+
+                fn mul(self, rhs: $matrix<$t>) -> $matrix<$t> {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
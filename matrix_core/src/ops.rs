@@ -0,0 +1,43 @@
+use crate::{MatrixError, Scalar};
+
+/// Operations shared by every `Matrix` implementation in the workspace.
+///
+/// Each crate keeps its own inherent methods (the tiled crates still take an
+/// explicit `block_size`), this trait only gives generic code a common entry
+/// point so implementations can be swapped without copy-pasting.
+pub trait MatrixOps: Sized {
+    type Elem: Scalar;
+
+    fn rows(&self) -> usize;
+
+    fn cols(&self) -> usize;
+
+    /// Element at row `i`, column `j`.
+    fn get(&self, i: usize, j: usize) -> Self::Elem;
+
+    fn zeros(rows: usize, cols: usize) -> Self;
+
+    fn random(rows: usize, cols: usize) -> Self;
+
+    fn identity(size: usize) -> Self;
+
+    fn add(&self, other: &Self) -> Self;
+
+    fn try_add(&self, other: &Self) -> Result<Self, MatrixError>;
+
+    fn subtract(&self, other: &Self) -> Self;
+
+    fn try_subtract(&self, other: &Self) -> Result<Self, MatrixError>;
+
+    fn multiply(&self, other: &Self) -> Self;
+
+    fn try_multiply(&self, other: &Self) -> Result<Self, MatrixError>;
+
+    fn hadamard_product(&self, other: &Self) -> Self;
+
+    fn try_hadamard_product(&self, other: &Self) -> Result<Self, MatrixError>;
+
+    fn transpose(&self) -> Self;
+
+    fn apply_function(&self, func: &dyn Fn(Self::Elem) -> Self::Elem) -> Self;
+}
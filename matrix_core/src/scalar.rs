@@ -0,0 +1,219 @@
+use rand::Rng;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+use crate::gemm::{self, MicroKernel};
+use crate::simd;
+
+/// Element type a `Matrix` can hold: `f64` (the default), `f32` and the
+/// integer types.
+///
+/// The `*_slices` and `micro_kernel` hooks are the kernels behind `add`,
+/// `subtract`, `hadamard_product` and `multiply_packed`; `f64` overrides them
+/// with the SIMD versions and `f32` overrides `micro_kernel`, every other
+/// type uses the portable loops. The `*_assign_slices` variants run the same
+/// kernels in place for `add_assign` and friends.
+pub trait Scalar:
+    Copy
+    + PartialEq
+    + Debug
+    + Display
+    + Send
+    + Sync
+    + 'static
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + AddAssign
+{
+    fn zero() -> Self;
+
+    fn one() -> Self;
+
+    /// Value used by `Matrix::random`: uniform in `[-1, 1)` for floats and a
+    /// small range for integers, so products of random matrices do not
+    /// overflow.
+    fn random() -> Self;
+
+    fn add_slices(a: &[Self], b: &[Self], out: &mut [Self]) {
+        for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+            *o = x + y;
+        }
+    }
+
+    fn sub_slices(a: &[Self], b: &[Self], out: &mut [Self]) {
+        for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+            *o = x - y;
+        }
+    }
+
+    fn mul_slices(a: &[Self], b: &[Self], out: &mut [Self]) {
+        for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+            *o = x * y;
+        }
+    }
+
+    /// `out[i] += b[i]` through `add_slices`.
+    fn add_assign_slices(out: &mut [Self], b: &[Self]) {
+        in_place(out, b, Self::add_slices);
+    }
+
+    /// `out[i] -= b[i]` through `sub_slices`.
+    fn sub_assign_slices(out: &mut [Self], b: &[Self]) {
+        in_place(out, b, Self::sub_slices);
+    }
+
+    /// `out[i] *= b[i]` through `mul_slices`.
+    fn mul_assign_slices(out: &mut [Self], b: &[Self]) {
+        in_place(out, b, Self::mul_slices);
+    }
+
+    fn micro_kernel() -> MicroKernel<Self> {
+        gemm::micro_kernel_scalar::<Self>
+    }
+
+    /// Whether `micro_kernel` uses SIMD instructions on this CPU. The plain
+    /// `multiply` of each crate goes through `gemm` only then, and keeps its
+    /// own loops otherwise.
+    fn has_simd_kernel() -> bool {
+        false
+    }
+}
+
+impl Scalar for f64 {
+    fn zero() -> f64 {
+        0.0
+    }
+
+    fn one() -> f64 {
+        1.0
+    }
+
+    fn random() -> f64 {
+        rand::thread_rng().gen::<f64>() * 2.0 - 1.0
+    }
+
+    fn add_slices(a: &[f64], b: &[f64], out: &mut [f64]) {
+        simd::add(a, b, out);
+    }
+
+    fn sub_slices(a: &[f64], b: &[f64], out: &mut [f64]) {
+        simd::sub(a, b, out);
+    }
+
+    fn mul_slices(a: &[f64], b: &[f64], out: &mut [f64]) {
+        simd::mul(a, b, out);
+    }
+
+    fn micro_kernel() -> MicroKernel<f64> {
+        simd::micro_kernel_for(simd::level())
+    }
+
+    fn has_simd_kernel() -> bool {
+        simd::level() != simd::SimdLevel::Scalar
+    }
+}
+
+impl Scalar for f32 {
+    fn zero() -> f32 {
+        0.0
+    }
+
+    fn one() -> f32 {
+        1.0
+    }
+
+    fn random() -> f32 {
+        rand::thread_rng().gen::<f32>() * 2.0 - 1.0
+    }
+
+    fn micro_kernel() -> MicroKernel<f32> {
+        simd::micro_kernel_f32_for(simd::level())
+    }
+
+    fn has_simd_kernel() -> bool {
+        simd::level() != simd::SimdLevel::Scalar
+    }
+}
+
+/// Elements copied to the stack per call of the kernel in `in_place`.
+const IN_PLACE_CHUNK: usize = 256;
+
+/// Runs `kernel(out, b, out)` a chunk at a time. The kernels take separate
+/// input and output slices, so each chunk of `out` is copied first.
+fn in_place<T: Scalar>(out: &mut [T], b: &[T], kernel: fn(&[T], &[T], &mut [T])) {
+    assert_eq!(out.len(), b.len());
+
+    let mut staged = [T::zero(); IN_PLACE_CHUNK];
+    for (out, b) in out.chunks_mut(IN_PLACE_CHUNK).zip(b.chunks(IN_PLACE_CHUNK)) {
+        let staged = &mut staged[..out.len()];
+        staged.copy_from_slice(out);
+        kernel(staged, b, out);
+    }
+}
+
+macro_rules! impl_scalar_for_integer {
+    ($($t:ty => $range:expr),*) => {
+        $(
+            impl Scalar for $t {
+                fn zero() -> $t {
+                    0
+                }
+
+                fn one() -> $t {
+                    1
+                }
+
+                fn random() -> $t {
+                    rand::thread_rng().gen_range($range)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_for_integer!(i32 => -10..=10, i64 => -10..=10, u32 => 0..=10, u64 => 0..=10);
+
+/// Floating point scalars, needed by the factorizations (pivoting compares
+/// magnitudes, Cholesky and Householder take square roots).
+pub trait Real: Scalar + PartialOrd + Neg<Output = Self> + Div<Output = Self> {
+    fn abs(self) -> Self;
+
+    fn sqrt(self) -> Self;
+
+    fn epsilon() -> Self;
+
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_real {
+    ($($t:ty),*) => {
+        $(
+            impl Real for $t {
+                fn abs(self) -> $t {
+                    <$t>::abs(self)
+                }
+
+                fn sqrt(self) -> $t {
+                    <$t>::sqrt(self)
+                }
+
+                fn epsilon() -> $t {
+                    <$t>::EPSILON
+                }
+
+                fn from_f64(value: f64) -> $t {
+                    value as $t
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_real!(f32, f64);
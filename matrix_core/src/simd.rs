@@ -0,0 +1,339 @@
+// Kernels com intrínsecos explícitos. O nível é escolhido em tempo de
+// execução com `is_x86_feature_detected!`, então o mesmo binário roda em
+// qualquer x86_64 (SSE2 faz parte da base da arquitetura) e os outros alvos
+// usam a versão escalar.
+
+use crate::gemm::{self, MicroKernel};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SimdLevel {
+    Avx2Fma,
+    Sse2,
+    Scalar,
+}
+
+/// Best instruction set available on the running CPU.
+pub fn level() -> SimdLevel {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            return SimdLevel::Avx2Fma;
+        }
+        if is_x86_feature_detected!("sse2") {
+            return SimdLevel::Sse2;
+        }
+    }
+    SimdLevel::Scalar
+}
+
+/// Levels that can run on this CPU, best first. Used to test every path.
+pub fn available_levels() -> Vec<SimdLevel> {
+    [SimdLevel::Avx2Fma, SimdLevel::Sse2, SimdLevel::Scalar]
+        .into_iter()
+        .filter(|&l| level_rank(l) >= level_rank(level()))
+        .collect()
+}
+
+/// `level` if the CPU has it, otherwise `level()`. Running a kernel the CPU
+/// lacks is undefined behaviour, so every forced level goes through here.
+fn supported(level: SimdLevel) -> SimdLevel {
+    let best = self::level();
+    if level_rank(level) >= level_rank(best) {
+        level
+    } else {
+        best
+    }
+}
+
+fn level_rank(level: SimdLevel) -> u8 {
+    match level {
+        SimdLevel::Avx2Fma => 0,
+        SimdLevel::Sse2 => 1,
+        SimdLevel::Scalar => 2,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+}
+
+/// `out[i] = a[i] + b[i]`
+pub fn add(a: &[f64], b: &[f64], out: &mut [f64]) {
+    elementwise_with(level(), Op::Add, a, b, out);
+}
+
+/// `out[i] = a[i] - b[i]`
+pub fn sub(a: &[f64], b: &[f64], out: &mut [f64]) {
+    elementwise_with(level(), Op::Sub, a, b, out);
+}
+
+/// `out[i] = a[i] * b[i]`
+pub fn mul(a: &[f64], b: &[f64], out: &mut [f64]) {
+    elementwise_with(level(), Op::Mul, a, b, out);
+}
+
+/// `add` with a forced instruction set. Levels the CPU lacks fall back to
+/// `level()`.
+pub fn add_with(level: SimdLevel, a: &[f64], b: &[f64], out: &mut [f64]) {
+    elementwise_with(level, Op::Add, a, b, out);
+}
+
+/// `sub` with a forced instruction set, clamped like `add_with`.
+pub fn sub_with(level: SimdLevel, a: &[f64], b: &[f64], out: &mut [f64]) {
+    elementwise_with(level, Op::Sub, a, b, out);
+}
+
+/// `mul` with a forced instruction set, clamped like `add_with`.
+pub fn mul_with(level: SimdLevel, a: &[f64], b: &[f64], out: &mut [f64]) {
+    elementwise_with(level, Op::Mul, a, b, out);
+}
+
+fn elementwise_with(level: SimdLevel, op: Op, a: &[f64], b: &[f64], out: &mut [f64]) {
+    assert!(a.len() == out.len() && b.len() == out.len());
+
+    match supported(level) {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2Fma => unsafe { x86::elementwise_avx2(op, a, b, out) },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => unsafe { x86::elementwise_sse2(op, a, b, out) },
+        _ => elementwise_scalar(op, a, b, out),
+    }
+}
+
+fn elementwise_scalar(op: Op, a: &[f64], b: &[f64], out: &mut [f64]) {
+    for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+        *o = match op {
+            Op::Add => x + y,
+            Op::Sub => x - y,
+            Op::Mul => x * y,
+        };
+    }
+}
+
+pub(crate) fn micro_kernel_for(level: SimdLevel) -> MicroKernel<f64> {
+    match supported(level) {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2Fma => {
+            |kc, a, b, c, ldc, mr, nr| unsafe { x86::micro_kernel_avx2(kc, a, b, c, ldc, mr, nr) }
+        }
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => {
+            |kc, a, b, c, ldc, mr, nr| unsafe { x86::micro_kernel_sse2(kc, a, b, c, ldc, mr, nr) }
+        }
+        _ => gemm::micro_kernel_scalar::<f64>,
+    }
+}
+
+/// `micro_kernel_for` for `f32`, with eight lanes per register.
+pub(crate) fn micro_kernel_f32_for(level: SimdLevel) -> MicroKernel<f32> {
+    match supported(level) {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2Fma => |kc, a, b, c, ldc, mr, nr| unsafe {
+            x86::micro_kernel_f32_avx2(kc, a, b, c, ldc, mr, nr)
+        },
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Sse2 => |kc, a, b, c, ldc, mr, nr| unsafe {
+            x86::micro_kernel_f32_sse2(kc, a, b, c, ldc, mr, nr)
+        },
+        _ => gemm::micro_kernel_scalar::<f32>,
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::Op;
+    use crate::gemm::{store, MR, NR};
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx2,fma")]
+    pub(super) unsafe fn elementwise_avx2(op: Op, a: &[f64], b: &[f64], out: &mut [f64]) {
+        let n = out.len();
+        let mut i = 0;
+
+        while i + 4 <= n {
+            let x = _mm256_loadu_pd(a.as_ptr().add(i));
+            let y = _mm256_loadu_pd(b.as_ptr().add(i));
+            let r = match op {
+                Op::Add => _mm256_add_pd(x, y),
+                Op::Sub => _mm256_sub_pd(x, y),
+                Op::Mul => _mm256_mul_pd(x, y),
+            };
+            _mm256_storeu_pd(out.as_mut_ptr().add(i), r);
+            i += 4;
+        }
+
+        super::elementwise_scalar(op, &a[i..], &b[i..], &mut out[i..]);
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn elementwise_sse2(op: Op, a: &[f64], b: &[f64], out: &mut [f64]) {
+        let n = out.len();
+        let mut i = 0;
+
+        while i + 2 <= n {
+            let x = _mm_loadu_pd(a.as_ptr().add(i));
+            let y = _mm_loadu_pd(b.as_ptr().add(i));
+            let r = match op {
+                Op::Add => _mm_add_pd(x, y),
+                Op::Sub => _mm_sub_pd(x, y),
+                Op::Mul => _mm_mul_pd(x, y),
+            };
+            _mm_storeu_pd(out.as_mut_ptr().add(i), r);
+            i += 2;
+        }
+
+        super::elementwise_scalar(op, &a[i..], &b[i..], &mut out[i..]);
+    }
+
+    // Cada linha do bloco 4 x 8 ocupa dois registradores de 256 bits:
+    // 8 acumuladores + 2 de B + 1 broadcast de A cabem nos 16 ymm.
+    #[target_feature(enable = "avx2,fma")]
+    pub(super) unsafe fn micro_kernel_avx2(
+        kc: usize,
+        a_panel: &[f64],
+        b_panel: &[f64],
+        c: &mut [f64],
+        ldc: usize,
+        mr: usize,
+        nr: usize,
+    ) {
+        assert!(a_panel.len() >= kc * MR && b_panel.len() >= kc * NR);
+
+        let mut acc = [[_mm256_setzero_pd(); 2]; MR];
+        let a = a_panel.as_ptr();
+        let b = b_panel.as_ptr();
+
+        for p in 0..kc {
+            let b0 = _mm256_loadu_pd(b.add(p * NR));
+            let b1 = _mm256_loadu_pd(b.add(p * NR + 4));
+
+            for (i, row) in acc.iter_mut().enumerate() {
+                let a_i = _mm256_broadcast_sd(&*a.add(p * MR + i));
+                row[0] = _mm256_fmadd_pd(a_i, b0, row[0]);
+                row[1] = _mm256_fmadd_pd(a_i, b1, row[1]);
+            }
+        }
+
+        let mut result = [[0.0; NR]; MR];
+        for (i, row) in acc.iter().enumerate() {
+            _mm256_storeu_pd(result[i].as_mut_ptr(), row[0]);
+            _mm256_storeu_pd(result[i].as_mut_ptr().add(4), row[1]);
+        }
+
+        store(&result, c, ldc, mr, nr);
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn micro_kernel_sse2(
+        kc: usize,
+        a_panel: &[f64],
+        b_panel: &[f64],
+        c: &mut [f64],
+        ldc: usize,
+        mr: usize,
+        nr: usize,
+    ) {
+        assert!(a_panel.len() >= kc * MR && b_panel.len() >= kc * NR);
+
+        let mut acc = [[_mm_setzero_pd(); NR / 2]; MR];
+        let a = a_panel.as_ptr();
+        let b = b_panel.as_ptr();
+
+        for p in 0..kc {
+            let mut b_regs = [_mm_setzero_pd(); NR / 2];
+            for (j, reg) in b_regs.iter_mut().enumerate() {
+                *reg = _mm_loadu_pd(b.add(p * NR + 2 * j));
+            }
+
+            for (i, row) in acc.iter_mut().enumerate() {
+                let a_i = _mm_set1_pd(*a.add(p * MR + i));
+                for (acc_ij, &b_j) in row.iter_mut().zip(&b_regs) {
+                    *acc_ij = _mm_add_pd(*acc_ij, _mm_mul_pd(a_i, b_j));
+                }
+            }
+        }
+
+        let mut result = [[0.0; NR]; MR];
+        for (i, row) in acc.iter().enumerate() {
+            for (j, reg) in row.iter().enumerate() {
+                _mm_storeu_pd(result[i].as_mut_ptr().add(2 * j), *reg);
+            }
+        }
+
+        store(&result, c, ldc, mr, nr);
+    }
+
+    // Em `f32` uma linha do bloco 4 x 8 cabe num registrador de 256 bits
+    #[target_feature(enable = "avx2,fma")]
+    pub(super) unsafe fn micro_kernel_f32_avx2(
+        kc: usize,
+        a_panel: &[f32],
+        b_panel: &[f32],
+        c: &mut [f32],
+        ldc: usize,
+        mr: usize,
+        nr: usize,
+    ) {
+        assert!(a_panel.len() >= kc * MR && b_panel.len() >= kc * NR);
+
+        let mut acc = [_mm256_setzero_ps(); MR];
+        let a = a_panel.as_ptr();
+        let b = b_panel.as_ptr();
+
+        for p in 0..kc {
+            let b_p = _mm256_loadu_ps(b.add(p * NR));
+
+            for (i, row) in acc.iter_mut().enumerate() {
+                let a_i = _mm256_broadcast_ss(&*a.add(p * MR + i));
+                *row = _mm256_fmadd_ps(a_i, b_p, *row);
+            }
+        }
+
+        let mut result = [[0.0; NR]; MR];
+        for (i, row) in acc.iter().enumerate() {
+            _mm256_storeu_ps(result[i].as_mut_ptr(), *row);
+        }
+
+        store(&result, c, ldc, mr, nr);
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn micro_kernel_f32_sse2(
+        kc: usize,
+        a_panel: &[f32],
+        b_panel: &[f32],
+        c: &mut [f32],
+        ldc: usize,
+        mr: usize,
+        nr: usize,
+    ) {
+        assert!(a_panel.len() >= kc * MR && b_panel.len() >= kc * NR);
+
+        let mut acc = [[_mm_setzero_ps(); NR / 4]; MR];
+        let a = a_panel.as_ptr();
+        let b = b_panel.as_ptr();
+
+        for p in 0..kc {
+            let b_regs = [_mm_loadu_ps(b.add(p * NR)), _mm_loadu_ps(b.add(p * NR + 4))];
+
+            for (i, row) in acc.iter_mut().enumerate() {
+                let a_i = _mm_set1_ps(*a.add(p * MR + i));
+                for (acc_ij, &b_j) in row.iter_mut().zip(&b_regs) {
+                    *acc_ij = _mm_add_ps(*acc_ij, _mm_mul_ps(a_i, b_j));
+                }
+            }
+        }
+
+        let mut result = [[0.0; NR]; MR];
+        for (i, row) in acc.iter().enumerate() {
+            for (j, reg) in row.iter().enumerate() {
+                _mm_storeu_ps(result[i].as_mut_ptr().add(4 * j), *reg);
+            }
+        }
+
+        store(&result, c, ldc, mr, nr);
+    }
+}
@@ -0,0 +1,680 @@
+// Matrizes esparsas em três formatos, para grafos e FEM onde quase tudo é
+// zero:
+//
+// * COO: triplas `(i, j, valor)` em qualquer ordem, boa para montar a matriz.
+// * CSR: linhas comprimidas. `row_offsets[i]..row_offsets[i + 1]` são as
+//   posições da linha `i` em `col_indices`/`values`. É o formato das contas
+//   (SpMV, produto por densa, soma), já que cada linha é contígua.
+// * CSC: o mesmo por colunas. Os vetores da CSC de A são os da CSR de Aᵀ, e
+//   é assim que a transposta é feita.
+//
+// As conversões para CSR ordenam as colunas de cada linha e somam entradas
+// repetidas. Zeros explícitos são mantidos.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{MatrixError, MatrixView, Scalar};
+
+/// The fields are private, so entries only come in through `try_push`
+/// (directly or via `try_from_triplets`) and are always inside the matrix;
+/// deserializing checks the same.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "CooParts<T>",
+    bound(deserialize = "T: Scalar + Deserialize<'de>")
+)]
+pub struct CooMatrix<T> {
+    rows: usize,
+    cols: usize,
+    row_indices: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// The fields are private and deserializing goes through `try_new`, so a
+/// CSR read from disk has the same invariants as one built here.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "CsrParts<T>",
+    bound(deserialize = "T: Scalar + Deserialize<'de>")
+)]
+pub struct CsrMatrix<T> {
+    rows: usize,
+    cols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+/// Deserializing goes through `try_new`, like `CsrMatrix`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(
+    try_from = "CscParts<T>",
+    bound(deserialize = "T: Scalar + Deserialize<'de>")
+)]
+pub struct CscMatrix<T> {
+    rows: usize,
+    cols: usize,
+    col_offsets: Vec<usize>,
+    row_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+// campos como vieram do arquivo, ainda sem validar
+#[derive(Deserialize)]
+struct CooParts<T> {
+    rows: usize,
+    cols: usize,
+    row_indices: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct CsrParts<T> {
+    rows: usize,
+    cols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct CscParts<T> {
+    rows: usize,
+    cols: usize,
+    col_offsets: Vec<usize>,
+    row_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Scalar> TryFrom<CooParts<T>> for CooMatrix<T> {
+    type Error = MatrixError;
+
+    fn try_from(parts: CooParts<T>) -> Result<CooMatrix<T>, MatrixError> {
+        let nnz = parts.values.len();
+        if parts.row_indices.len() != nnz || parts.col_indices.len() != nnz {
+            return Err(MatrixError::InvalidSparseStructure {
+                reason: "indices and values disagree on the number of entries",
+            });
+        }
+
+        let mut coo = CooMatrix::new(parts.rows, parts.cols);
+        for ((&i, &j), &value) in parts
+            .row_indices
+            .iter()
+            .zip(&parts.col_indices)
+            .zip(&parts.values)
+        {
+            coo.try_push(i, j, value)?;
+        }
+        Ok(coo)
+    }
+}
+
+impl<T: Scalar> TryFrom<CsrParts<T>> for CsrMatrix<T> {
+    type Error = MatrixError;
+
+    fn try_from(parts: CsrParts<T>) -> Result<CsrMatrix<T>, MatrixError> {
+        CsrMatrix::try_new(
+            parts.rows,
+            parts.cols,
+            parts.row_offsets,
+            parts.col_indices,
+            parts.values,
+        )
+    }
+}
+
+impl<T: Scalar> TryFrom<CscParts<T>> for CscMatrix<T> {
+    type Error = MatrixError;
+
+    fn try_from(parts: CscParts<T>) -> Result<CscMatrix<T>, MatrixError> {
+        CscMatrix::try_new(
+            parts.rows,
+            parts.cols,
+            parts.col_offsets,
+            parts.row_indices,
+            parts.values,
+        )
+    }
+}
+
+impl<T: Scalar> CooMatrix<T> {
+    pub fn new(rows: usize, cols: usize) -> CooMatrix<T> {
+        CooMatrix {
+            rows,
+            cols,
+            row_indices: Vec::new(),
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn try_from_triplets(
+        rows: usize,
+        cols: usize,
+        triplets: &[(usize, usize, T)],
+    ) -> Result<CooMatrix<T>, MatrixError> {
+        let mut coo = CooMatrix::new(rows, cols);
+        for &(i, j, value) in triplets {
+            coo.try_push(i, j, value)?;
+        }
+        Ok(coo)
+    }
+
+    pub fn push(&mut self, i: usize, j: usize, value: T) {
+        self.try_push(i, j, value)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_push(&mut self, i: usize, j: usize, value: T) -> Result<(), MatrixError> {
+        if i >= self.rows || j >= self.cols {
+            return Err(MatrixError::InvalidSparseStructure {
+                reason: "entry outside the matrix",
+            });
+        }
+        self.row_indices.push(i);
+        self.col_indices.push(j);
+        self.values.push(value);
+        Ok(())
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row_indices(&self) -> &[usize] {
+        &self.row_indices
+    }
+
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Entries of a row-major `rows x cols` buffer that are not zero.
+    pub fn from_dense(data: &[T], rows: usize, cols: usize) -> CooMatrix<T> {
+        let mut coo = CooMatrix::new(rows, cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                if data[i * cols + j] != T::zero() {
+                    coo.push(i, j, data[i * cols + j]);
+                }
+            }
+        }
+        coo
+    }
+
+    /// Row-major dense copy, repeated entries summed.
+    pub fn to_dense(&self) -> Vec<T> {
+        let mut data = vec![T::zero(); self.rows * self.cols];
+        for ((&i, &j), &value) in self
+            .row_indices
+            .iter()
+            .zip(&self.col_indices)
+            .zip(&self.values)
+        {
+            data[i * self.cols + j] += value;
+        }
+        data
+    }
+
+    /// Counting sort by row, then each row sorted by column with repeated
+    /// entries summed.
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let (row_offsets, order) = bucket(self.rows, &self.row_indices);
+
+        let mut col_indices = Vec::with_capacity(self.nnz());
+        let mut values = Vec::with_capacity(self.nnz());
+        let mut offsets = Vec::with_capacity(self.rows + 1);
+        offsets.push(0);
+
+        for i in 0..self.rows {
+            let mut row: Vec<(usize, T)> = order[row_offsets[i]..row_offsets[i + 1]]
+                .iter()
+                .map(|&k| (self.col_indices[k], self.values[k]))
+                .collect();
+            row.sort_by_key(|&(j, _)| j);
+
+            for (j, value) in row {
+                match col_indices.last() {
+                    Some(&last) if last == j && col_indices.len() > offsets[i] => {
+                        *values.last_mut().unwrap() += value;
+                    }
+                    _ => {
+                        col_indices.push(j);
+                        values.push(value);
+                    }
+                }
+            }
+            offsets.push(col_indices.len());
+        }
+
+        CsrMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            row_offsets: offsets,
+            col_indices,
+            values,
+        }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        self.to_csr().to_csc()
+    }
+}
+
+/// Offsets of each bucket and the positions sorted by bucket, stable.
+fn bucket(buckets: usize, keys: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let mut offsets = vec![0; buckets + 1];
+    for &key in keys {
+        offsets[key + 1] += 1;
+    }
+    for b in 0..buckets {
+        offsets[b + 1] += offsets[b];
+    }
+
+    let mut next = offsets.clone();
+    let mut order = vec![0; keys.len()];
+    for (position, &key) in keys.iter().enumerate() {
+        order[next[key]] = position;
+        next[key] += 1;
+    }
+
+    (offsets, order)
+}
+
+/// Checks the compressed arrays shared by CSR and CSC: `outer + 1` offsets
+/// starting at 0 and never decreasing, and inner indices below `inner`,
+/// strictly increasing inside each slice.
+fn check_compressed(
+    outer: usize,
+    inner: usize,
+    offsets: &[usize],
+    indices: &[usize],
+    values_len: usize,
+) -> Result<(), MatrixError> {
+    let invalid = |reason| Err(MatrixError::InvalidSparseStructure { reason });
+
+    if offsets.len().checked_sub(1) != Some(outer) || offsets[0] != 0 {
+        return invalid("offsets must have one entry per row or column plus one, starting at 0");
+    }
+    if indices.len() != values_len || offsets[outer] != values_len {
+        return invalid("offsets, indices and values disagree on the number of entries");
+    }
+    for k in 0..outer {
+        let (start, end) = (offsets[k], offsets[k + 1]);
+        if start > end || end > values_len {
+            return invalid("offsets must not decrease");
+        }
+        let slice = &indices[start..end];
+        if slice.iter().any(|&index| index >= inner) {
+            return invalid("index outside the matrix");
+        }
+        if slice.windows(2).any(|w| w[0] >= w[1]) {
+            return invalid("indices must be sorted without repetitions");
+        }
+    }
+    Ok(())
+}
+
+impl<T: Scalar> CsrMatrix<T> {
+    pub fn try_new(
+        rows: usize,
+        cols: usize,
+        row_offsets: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<CsrMatrix<T>, MatrixError> {
+        check_compressed(rows, cols, &row_offsets, &col_indices, values.len())?;
+
+        Ok(CsrMatrix {
+            rows,
+            cols,
+            row_offsets,
+            col_indices,
+            values,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> CsrMatrix<T> {
+        CsrMatrix {
+            rows,
+            cols,
+            row_offsets: vec![0; rows + 1],
+            col_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn identity(size: usize) -> CsrMatrix<T> {
+        CsrMatrix {
+            rows: size,
+            cols: size,
+            row_offsets: (0..=size).collect(),
+            col_indices: (0..size).collect(),
+            values: vec![T::one(); size],
+        }
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Column indices and values of row `i`.
+    pub fn row(&self, i: usize) -> (&[usize], &[T]) {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        (&self.col_indices[range.clone()], &self.values[range])
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> T {
+        let (cols, values) = self.row(i);
+        cols.binary_search(&j).map_or(T::zero(), |k| values[k])
+    }
+
+    pub fn from_dense(data: &[T], rows: usize, cols: usize) -> CsrMatrix<T> {
+        CooMatrix::from_dense(data, rows, cols).to_csr()
+    }
+
+    pub fn to_dense(&self) -> Vec<T> {
+        let mut data = vec![T::zero(); self.rows * self.cols];
+        for i in 0..self.rows {
+            let (cols, values) = self.row(i);
+            for (&j, &value) in cols.iter().zip(values) {
+                data[i * self.cols + j] = value;
+            }
+        }
+        data
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let mut row_indices = Vec::with_capacity(self.nnz());
+        for i in 0..self.rows {
+            row_indices.extend(std::iter::repeat_n(
+                i,
+                self.row_offsets[i + 1] - self.row_offsets[i],
+            ));
+        }
+
+        CooMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            row_indices,
+            col_indices: self.col_indices.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Counting sort by column. Rows stay sorted inside each column because
+    /// the entries are visited row by row.
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let (col_offsets, order) = bucket(self.cols, &self.col_indices);
+        let row_of = self.to_coo().row_indices;
+
+        CscMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            col_offsets,
+            row_indices: order.iter().map(|&k| row_of[k]).collect(),
+            values: order.iter().map(|&k| self.values[k]).collect(),
+        }
+    }
+
+    pub fn transpose(&self) -> CsrMatrix<T> {
+        self.to_csc().into_transposed_csr()
+    }
+
+    /// `y = A * x`.
+    pub fn spmv(&self, x: &[T]) -> Result<Vec<T>, MatrixError> {
+        self.check_vector(x)?;
+
+        let mut y = vec![T::zero(); self.rows];
+        for (i, y_i) in y.iter_mut().enumerate() {
+            *y_i = self.row_dot(i, x);
+        }
+        Ok(y)
+    }
+
+    /// `A[i] · x`, the kernel of `spmv` for one row. Used by the parallel
+    /// crates to split the rows between threads.
+    pub fn row_dot(&self, i: usize, x: &[T]) -> T {
+        let (cols, values) = self.row(i);
+        let mut sum = T::zero();
+        for (&j, &value) in cols.iter().zip(values) {
+            sum += value * x[j];
+        }
+        sum
+    }
+
+    pub fn check_vector(&self, x: &[T]) -> Result<(), MatrixError> {
+        if x.len() != self.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (x.len(), 1),
+            });
+        }
+        Ok(())
+    }
+
+    /// `A * B` for a dense `B`, row-major `rows x b.cols()`.
+    pub fn multiply_dense(&self, b: &MatrixView<T>) -> Result<Vec<T>, MatrixError> {
+        self.check_dense(b)?;
+
+        let mut c = vec![T::zero(); self.rows * b.cols()];
+        for (i, c_row) in c.chunks_mut(b.cols().max(1)).enumerate() {
+            self.row_times_dense(i, b, c_row);
+        }
+        Ok(c)
+    }
+
+    /// Row `i` of `A * B` into `c_row`: each entry `A[i][k]` scales row `k`
+    /// of B.
+    pub fn row_times_dense(&self, i: usize, b: &MatrixView<T>, c_row: &mut [T]) {
+        let (cols, values) = self.row(i);
+        for (&k, &a_ik) in cols.iter().zip(values) {
+            match b.row_slice(k) {
+                Some(b_row) => {
+                    for (c, &b_kj) in c_row.iter_mut().zip(b_row) {
+                        *c += a_ik * b_kj;
+                    }
+                }
+                None => {
+                    for (j, c) in c_row.iter_mut().enumerate() {
+                        *c += a_ik * b[(k, j)];
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn check_dense(&self, b: &MatrixView<T>) -> Result<(), MatrixError> {
+        if self.cols != b.rows() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (b.rows(), b.cols()),
+            });
+        }
+        Ok(())
+    }
+
+    /// `A + B`, merging the sorted columns of each row.
+    pub fn add(&self, other: &CsrMatrix<T>) -> Result<CsrMatrix<T>, MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        let mut row_offsets = Vec::with_capacity(self.rows + 1);
+        let mut col_indices = Vec::with_capacity(self.nnz() + other.nnz());
+        let mut values = Vec::with_capacity(self.nnz() + other.nnz());
+        row_offsets.push(0);
+
+        for i in 0..self.rows {
+            let (a_cols, a_values) = self.row(i);
+            let (b_cols, b_values) = other.row(i);
+            let (mut p, mut q) = (0, 0);
+
+            while p < a_cols.len() || q < b_cols.len() {
+                let a_col = a_cols.get(p).copied().unwrap_or(usize::MAX);
+                let b_col = b_cols.get(q).copied().unwrap_or(usize::MAX);
+
+                if a_col == b_col {
+                    col_indices.push(a_col);
+                    values.push(a_values[p] + b_values[q]);
+                    p += 1;
+                    q += 1;
+                } else if a_col < b_col {
+                    col_indices.push(a_col);
+                    values.push(a_values[p]);
+                    p += 1;
+                } else {
+                    col_indices.push(b_col);
+                    values.push(b_values[q]);
+                    q += 1;
+                }
+            }
+            row_offsets.push(col_indices.len());
+        }
+
+        Ok(CsrMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            row_offsets,
+            col_indices,
+            values,
+        })
+    }
+}
+
+impl<T: Scalar> CscMatrix<T> {
+    pub fn try_new(
+        rows: usize,
+        cols: usize,
+        col_offsets: Vec<usize>,
+        row_indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<CscMatrix<T>, MatrixError> {
+        check_compressed(cols, rows, &col_offsets, &row_indices, values.len())?;
+
+        Ok(CscMatrix {
+            rows,
+            cols,
+            col_offsets,
+            row_indices,
+            values,
+        })
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn col_offsets(&self) -> &[usize] {
+        &self.col_offsets
+    }
+
+    pub fn row_indices(&self) -> &[usize] {
+        &self.row_indices
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn from_dense(data: &[T], rows: usize, cols: usize) -> CscMatrix<T> {
+        CsrMatrix::from_dense(data, rows, cols).to_csc()
+    }
+
+    pub fn to_dense(&self) -> Vec<T> {
+        let mut data = vec![T::zero(); self.rows * self.cols];
+        for j in 0..self.cols {
+            for k in self.col_offsets[j]..self.col_offsets[j + 1] {
+                data[self.row_indices[k] * self.cols + j] = self.values[k];
+            }
+        }
+        data
+    }
+
+    /// The same arrays read as the CSR of `Aᵀ`, without copying.
+    pub fn into_transposed_csr(self) -> CsrMatrix<T> {
+        CsrMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_offsets: self.col_offsets,
+            col_indices: self.row_indices,
+            values: self.values,
+        }
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        self.clone().into_transposed_csr().transpose()
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        self.to_csr().to_coo()
+    }
+
+    /// `y = A * x`, adding each column scaled by `x[j]`.
+    pub fn spmv(&self, x: &[T]) -> Result<Vec<T>, MatrixError> {
+        if x.len() != self.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (x.len(), 1),
+            });
+        }
+
+        let mut y = vec![T::zero(); self.rows];
+        for (j, &x_j) in x.iter().enumerate() {
+            for k in self.col_offsets[j]..self.col_offsets[j + 1] {
+                y[self.row_indices[k]] += self.values[k] * x_j;
+            }
+        }
+        Ok(y)
+    }
+}
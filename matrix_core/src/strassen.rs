@@ -0,0 +1,156 @@
+// Strassen na variante de Winograd: 7 multiplicações e 15 somas por nível
+// (o Strassen original faz 18). As matrizes são completadas com zeros até
+// um tamanho `q * 2^d` com `q <= cutoff`, então cada nível divide ao meio
+// sem resto e a recursão para exatamente no kernel do crate.
+//
+// Aqui ficam as partes sequenciais: o padding, os fatores de cada nível, a
+// montagem de C e a recursão. O kernel das folhas vem de quem chama, e o
+// `contiguous_parallel_tiled` tem a própria recursão, que roda os sete
+// produtos com `rayon::join` em cima de `factors` e `combine`.
+
+use crate::Scalar;
+
+/// Smallest `q * 2^d >= n` with `q <= cutoff`, the padded size used by the
+/// recursion.
+pub fn padded_size(n: usize, cutoff: usize) -> usize {
+    let cutoff = cutoff.max(1);
+    let mut levels = 0;
+
+    while n.div_ceil(1 << levels) > cutoff {
+        levels += 1;
+    }
+
+    n.div_ceil(1 << levels) << levels
+}
+
+/// Copies the `rows x cols` matrix into the top-left corner of an `s x s`
+/// buffer of zeros.
+pub fn pad<T: Scalar>(data: &[T], rows: usize, cols: usize, s: usize) -> Vec<T> {
+    let mut padded = vec![T::zero(); s * s];
+
+    for i in 0..rows {
+        padded[i * s..i * s + cols].copy_from_slice(&data[i * cols..(i + 1) * cols]);
+    }
+
+    padded
+}
+
+pub fn unpad<T: Scalar>(padded: &[T], s: usize, rows: usize, cols: usize) -> Vec<T> {
+    let mut data = Vec::with_capacity(rows * cols);
+
+    for i in 0..rows {
+        data.extend_from_slice(&padded[i * s..i * s + cols]);
+    }
+
+    data
+}
+
+/// Whether an `s x s` product is split into quadrants instead of going to
+/// the kernel.
+pub fn splits(s: usize, cutoff: usize) -> bool {
+    s > cutoff && s.is_multiple_of(2)
+}
+
+/// `a * b` for `s x s` matrices, where `s` comes from `padded_size`.
+/// Products of at most `cutoff` rows go to `kernel(a, b, s)`.
+pub fn multiply<T, K>(a: &[T], b: &[T], s: usize, cutoff: usize, kernel: &K) -> Vec<T>
+where
+    T: Scalar,
+    K: Fn(&[T], &[T], usize) -> Vec<T>,
+{
+    if !splits(s, cutoff) {
+        return kernel(a, b, s);
+    }
+
+    let h = s / 2;
+    let products = factors(a, b, s).map(|(x, y)| multiply(&x, &y, h, cutoff, kernel));
+    combine(&products, h)
+}
+
+/// The seven `s/2 x s/2` factor pairs of one level, in the order `combine`
+/// expects the products: `A11 B11`, `A12 B21`, `S4 B22`, `A22 T4`, `S1 T1`,
+/// `S2 T2` and `S3 T3`.
+pub fn factors<T: Scalar>(a: &[T], b: &[T], s: usize) -> [(Vec<T>, Vec<T>); 7] {
+    let [a11, a12, a21, a22] = split(a, s);
+    let [b11, b12, b21, b22] = split(b, s);
+
+    let s1 = add(&a21, &a22);
+    let s2 = sub(&s1, &a11);
+    let s3 = sub(&a11, &a21);
+    let s4 = sub(&a12, &s2);
+    let t1 = sub(&b12, &b11);
+    let t2 = sub(&b22, &t1);
+    let t3 = sub(&b22, &b12);
+    let t4 = sub(&t2, &b21);
+
+    [
+        (a11, b11),
+        (a12, b21),
+        (s4, b22),
+        (a22, t4),
+        (s1, t1),
+        (s2, t2),
+        (s3, t3),
+    ]
+}
+
+/// Quadrants `[x11, x12, x21, x22]` of an `s x s` matrix.
+fn split<T: Scalar>(x: &[T], s: usize) -> [Vec<T>; 4] {
+    let h = s / 2;
+    let mut quadrants = [
+        Vec::with_capacity(h * h),
+        Vec::with_capacity(h * h),
+        Vec::with_capacity(h * h),
+        Vec::with_capacity(h * h),
+    ];
+
+    for i in 0..s {
+        let row = &x[i * s..(i + 1) * s];
+        let top = if i < h { 0 } else { 2 };
+        quadrants[top].extend_from_slice(&row[..h]);
+        quadrants[top + 1].extend_from_slice(&row[h..]);
+    }
+
+    quadrants
+}
+
+/// Builds `C` from the seven products `[P1, ..., P7]` of `factors`:
+/// `C11 = P1 + P2`, `C12 = P1 + P6 + P5 + P3`,
+/// `C21 = P1 + P6 + P7 - P4`, `C22 = P1 + P6 + P7 + P5`.
+pub fn combine<T: Scalar>(products: &[Vec<T>; 7], h: usize) -> Vec<T> {
+    let [p1, p2, p3, p4, p5, p6, p7] = products;
+
+    let c11 = add(p1, p2);
+    let u2 = add(p1, p6);
+    let u3 = add(&u2, p7);
+    let u4 = add(&u2, p5);
+    let c12 = add(&u4, p3);
+    let c21 = sub(&u3, p4);
+    let c22 = add(&u3, p5);
+
+    let s = 2 * h;
+    let mut c = Vec::with_capacity(s * s);
+
+    for i in 0..h {
+        c.extend_from_slice(&c11[i * h..(i + 1) * h]);
+        c.extend_from_slice(&c12[i * h..(i + 1) * h]);
+    }
+    for i in 0..h {
+        c.extend_from_slice(&c21[i * h..(i + 1) * h]);
+        c.extend_from_slice(&c22[i * h..(i + 1) * h]);
+    }
+
+    c
+}
+
+fn add<T: Scalar>(x: &[T], y: &[T]) -> Vec<T> {
+    let mut out = vec![T::zero(); x.len()];
+    T::add_slices(x, y, &mut out);
+    out
+}
+
+fn sub<T: Scalar>(x: &[T], y: &[T]) -> Vec<T> {
+    let mut out = vec![T::zero(); x.len()];
+    T::sub_slices(x, y, &mut out);
+    out
+}
@@ -0,0 +1,209 @@
+// Autotune dos crates tiled: mede o `multiply` com vários tamanhos de bloco
+// e ordens de loop e guarda o vencedor num arquivo local, por crate e tipo
+// de elemento (um bloco bom para `f64` é grande demais para `f32`):
+//
+//     [contiguous_tiled.f64]
+//     block_size = 128
+//     loop_order = ikj
+//
+// O arquivo é `matrix_tuning.conf` na raiz do workspace, qualquer que seja
+// o diretório de onde o binário roda, ou o caminho em `MATRIX_TUNING_FILE`.
+
+use std::any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Instant;
+
+pub const CONFIG_FILE: &str = "matrix_tuning.conf";
+pub const CONFIG_FILE_ENV: &str = "MATRIX_TUNING_FILE";
+
+/// Block sizes tried by the autotuner, from fitting L1 to fitting L2.
+pub const BLOCK_SIZE_CANDIDATES: [usize; 10] = [16, 32, 48, 64, 96, 128, 192, 256, 264, 512];
+
+/// Order of the three loops inside a tile of `C += A * B`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoopOrder {
+    /// `C[i][j]` is accumulated as a dot product, walking B down a column.
+    Ijk,
+    /// `A[i][k]` is broadcast along row `k` of B, walking every slice by row.
+    Ikj,
+}
+
+impl LoopOrder {
+    pub const ALL: [LoopOrder; 2] = [LoopOrder::Ijk, LoopOrder::Ikj];
+}
+
+impl fmt::Display for LoopOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoopOrder::Ijk => write!(f, "ijk"),
+            LoopOrder::Ikj => write!(f, "ikj"),
+        }
+    }
+}
+
+impl FromStr for LoopOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<LoopOrder, String> {
+        match s {
+            "ijk" => Ok(LoopOrder::Ijk),
+            "ikj" => Ok(LoopOrder::Ikj),
+            _ => Err(format!("Unknown loop order {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tuning {
+    pub block_size: usize,
+    pub loop_order: LoopOrder,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    pub block_size: usize,
+    pub loop_order: LoopOrder,
+    /// Best time of the repetitions.
+    pub seconds: f64,
+}
+
+/// Times `run(block_size, loop_order)` for every combination, keeping the
+/// best of `repetitions` runs after one warm-up, fastest first.
+pub fn sweep<F>(
+    block_sizes: &[usize],
+    loop_orders: &[LoopOrder],
+    repetitions: usize,
+    mut run: F,
+) -> Vec<Measurement>
+where
+    F: FnMut(usize, LoopOrder),
+{
+    let mut measurements = Vec::new();
+
+    for &block_size in block_sizes {
+        for &loop_order in loop_orders {
+            run(block_size, loop_order);
+
+            let mut best = f64::INFINITY;
+            for _ in 0..repetitions.max(1) {
+                let start = Instant::now();
+                run(block_size, loop_order);
+                best = best.min(start.elapsed().as_secs_f64());
+            }
+
+            measurements.push(Measurement {
+                block_size,
+                loop_order,
+                seconds: best,
+            });
+        }
+    }
+
+    measurements.sort_by(|a, b| a.seconds.total_cmp(&b.seconds));
+    measurements
+}
+
+/// `MATRIX_TUNING_FILE`, or `matrix_tuning.conf` at the workspace root.
+pub fn config_path() -> PathBuf {
+    std::env::var_os(CONFIG_FILE_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+            manifest_dir
+                .parent()
+                .unwrap_or(manifest_dir)
+                .join(CONFIG_FILE)
+        })
+}
+
+/// Section of the config file for `crate_name` multiplying `T`s, e.g.
+/// `contiguous_tiled.f64`.
+pub fn section<T>(crate_name: &str) -> String {
+    format!("{}.{}", crate_name, any::type_name::<T>())
+}
+
+/// Tuning saved for `name` in the config file, if any.
+pub fn load(name: &str) -> Option<Tuning> {
+    load_from(&config_path(), name)
+}
+
+/// Every section of the config file.
+pub fn load_all() -> BTreeMap<String, Tuning> {
+    fs::read_to_string(config_path())
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+pub fn load_from(path: &Path, name: &str) -> Option<Tuning> {
+    let contents = fs::read_to_string(path).ok()?;
+    parse(&contents).remove(name)
+}
+
+/// Saves the tuning for `name`, keeping the entries of the other crates.
+pub fn save(name: &str, tuning: Tuning) -> io::Result<PathBuf> {
+    let path = config_path();
+    save_to(&path, name, tuning)?;
+    Ok(path)
+}
+
+pub fn save_to(path: &Path, name: &str, tuning: Tuning) -> io::Result<()> {
+    let mut sections = fs::read_to_string(path)
+        .map(|contents| parse(&contents))
+        .unwrap_or_default();
+    sections.insert(name.to_string(), tuning);
+
+    let mut contents = String::from("# gerado pelo autotune, um bloco por crate e tipo\n");
+    for (name, tuning) in &sections {
+        contents.push_str(&format!(
+            "\n[{}]\nblock_size = {}\nloop_order = {}\n",
+            name, tuning.block_size, tuning.loop_order
+        ));
+    }
+
+    fs::write(path, contents)
+}
+
+/// Sections missing a key or with invalid values are skipped.
+fn parse(contents: &str) -> BTreeMap<String, Tuning> {
+    let mut sections = BTreeMap::new();
+    let mut name: Option<String> = None;
+    let mut block_size = None;
+    let mut loop_order = None;
+
+    let mut flush = |name: &Option<String>, block_size: Option<usize>, loop_order| {
+        if let (Some(name), Some(block_size), Some(loop_order)) = (name, block_size, loop_order) {
+            sections.insert(
+                name.clone(),
+                Tuning {
+                    block_size,
+                    loop_order,
+                },
+            );
+        }
+    };
+
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            flush(&name, block_size.take(), loop_order.take());
+            name = Some(section.trim().to_string());
+        } else if let Some((key, value)) = line.split_once('=') {
+            match key.trim() {
+                "block_size" => block_size = value.trim().parse().ok().filter(|&b| b > 0),
+                "loop_order" => loop_order = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+    }
+    flush(&name, block_size, loop_order);
+
+    sections
+}
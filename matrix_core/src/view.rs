@@ -0,0 +1,455 @@
+// Views emprestadas sobre o `Vec<T>` row-major de uma matriz, sem cópia. O
+// slice guardado começa no elemento `(0, 0)` da view e `(i, j)` fica em
+// `i * row_stride + j * col_stride`, então uma linha, uma coluna, um bloco ou
+// a transposta são só outro começo e outros strides sobre os mesmos dados.
+//
+// `MatrixViewMut::submatrix_mut` reempresta a view, então dá para recursar em
+// blocos de C um de cada vez (C11 += A11 * B11, depois C12...) sem alocar.
+
+use std::ops::{Index, IndexMut, Range};
+
+use crate::gemm;
+use crate::{MatrixError, Scalar};
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatrixView<'a, T> {
+    data: &'a [T],
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T> {
+    data: &'a mut [T],
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+/// Checks that a slice of `len` elements reaches the last element of the view.
+fn required_len(
+    len: usize,
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+) -> Result<(), MatrixError> {
+    if rows == 0 || cols == 0 {
+        return Ok(());
+    }
+
+    let expected = (rows - 1) * row_stride + (cols - 1) * col_stride + 1;
+    if len < expected {
+        return Err(MatrixError::InvalidDataLength {
+            expected,
+            actual: len,
+        });
+    }
+    Ok(())
+}
+
+fn check_range(range: &Range<usize>, len: usize, what: &str) {
+    assert!(
+        range.start <= range.end && range.end <= len,
+        "{} range {:?} out of bounds for {}",
+        what,
+        range,
+        len
+    );
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    pub fn new(
+        data: &'a [T],
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Result<MatrixView<'a, T>, MatrixError> {
+        required_len(data.len(), rows, cols, row_stride, col_stride)?;
+
+        Ok(MatrixView {
+            data,
+            rows,
+            cols,
+            row_stride,
+            col_stride,
+        })
+    }
+
+    /// The whole of a row-major `rows x cols` buffer.
+    pub fn from_row_major(data: &'a [T], rows: usize, cols: usize) -> MatrixView<'a, T> {
+        MatrixView::new(data, rows, cols, cols, 1).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row_stride(&self) -> usize {
+        self.row_stride
+    }
+
+    pub fn col_stride(&self) -> usize {
+        self.col_stride
+    }
+
+    /// Row `i` as a `1 x cols` view.
+    pub fn row(&self, i: usize) -> MatrixView<'a, T> {
+        self.submatrix(i..i + 1, 0..self.cols)
+    }
+
+    /// Column `j` as a `rows x 1` view.
+    pub fn col(&self, j: usize) -> MatrixView<'a, T> {
+        self.submatrix(0..self.rows, j..j + 1)
+    }
+
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'a, T> {
+        check_range(&rows, self.rows, "row");
+        check_range(&cols, self.cols, "column");
+
+        let (sub_rows, sub_cols) = (rows.len(), cols.len());
+        let data = if sub_rows == 0 || sub_cols == 0 {
+            &self.data[..0]
+        } else {
+            &self.data[rows.start * self.row_stride + cols.start * self.col_stride..]
+        };
+
+        MatrixView {
+            data,
+            rows: sub_rows,
+            cols: sub_cols,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    /// `Aᵀ` over the same data: rows and strides swapped.
+    pub fn transpose(&self) -> MatrixView<'a, T> {
+        MatrixView {
+            data: self.data,
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    /// Row `i` as a slice, when the columns are contiguous.
+    pub fn row_slice(&self, i: usize) -> Option<&'a [T]> {
+        if self.col_stride != 1 || i >= self.rows {
+            return None;
+        }
+        let start = i * self.row_stride;
+        Some(&self.data[start..start + self.cols])
+    }
+}
+
+impl<T: Copy> MatrixView<'_, T> {
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self[(i, j)]
+    }
+
+    /// Copy of the elements in row-major order.
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut data = Vec::with_capacity(self.rows * self.cols);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                data.push(self.data[i * self.row_stride + j * self.col_stride]);
+            }
+        }
+        data
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(
+            i < self.rows && j < self.cols,
+            "Index ({}, {}) out of bounds for a {}x{} view",
+            i,
+            j,
+            self.rows,
+            self.cols
+        );
+        &self.data[i * self.row_stride + j * self.col_stride]
+    }
+}
+
+impl<'a, T> MatrixViewMut<'a, T> {
+    pub fn new(
+        data: &'a mut [T],
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Result<MatrixViewMut<'a, T>, MatrixError> {
+        required_len(data.len(), rows, cols, row_stride, col_stride)?;
+
+        Ok(MatrixViewMut {
+            data,
+            rows,
+            cols,
+            row_stride,
+            col_stride,
+        })
+    }
+
+    pub fn from_row_major(data: &'a mut [T], rows: usize, cols: usize) -> MatrixViewMut<'a, T> {
+        MatrixViewMut::new(data, rows, cols, cols, 1).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            data: self.data,
+            rows: self.rows,
+            cols: self.cols,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> MatrixViewMut<'_, T> {
+        let cols = self.cols;
+        self.submatrix_mut(i..i + 1, 0..cols)
+    }
+
+    pub fn col_mut(&mut self, j: usize) -> MatrixViewMut<'_, T> {
+        let rows = self.rows;
+        self.submatrix_mut(0..rows, j..j + 1)
+    }
+
+    /// Mutable block, borrowing this view until it is dropped.
+    pub fn submatrix_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, T> {
+        MatrixViewMut {
+            data: &mut *self.data,
+            rows: self.rows,
+            cols: self.cols,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+        .into_submatrix(rows, cols)
+    }
+
+    /// `submatrix_mut` consuming the view, keeping the original borrow.
+    pub fn into_submatrix(self, rows: Range<usize>, cols: Range<usize>) -> MatrixViewMut<'a, T> {
+        check_range(&rows, self.rows, "row");
+        check_range(&cols, self.cols, "column");
+
+        let (sub_rows, sub_cols) = (rows.len(), cols.len());
+        let data = if sub_rows == 0 || sub_cols == 0 {
+            &mut self.data[..0]
+        } else {
+            &mut self.data[rows.start * self.row_stride + cols.start * self.col_stride..]
+        };
+
+        MatrixViewMut {
+            data,
+            rows: sub_rows,
+            cols: sub_cols,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    pub fn transpose_mut(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut {
+            data: self.data,
+            rows: self.cols,
+            cols: self.rows,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+}
+
+impl<T: Copy> MatrixViewMut<'_, T> {
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self[(i, j)]
+    }
+
+    pub fn fill(&mut self, value: T) {
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                self.data[i * self.row_stride + j * self.col_stride] = value;
+            }
+        }
+    }
+
+    pub fn copy_from(&mut self, other: &MatrixView<T>) -> Result<(), MatrixError> {
+        check_shape("copy", self.rows, self.cols, other)?;
+
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                self.data[i * self.row_stride + j * self.col_stride] = other[(i, j)];
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(
+            i < self.rows && j < self.cols,
+            "Index ({}, {}) out of bounds for a {}x{} view",
+            i,
+            j,
+            self.rows,
+            self.cols
+        );
+        &self.data[i * self.row_stride + j * self.col_stride]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(
+            i < self.rows && j < self.cols,
+            "Index ({}, {}) out of bounds for a {}x{} view",
+            i,
+            j,
+            self.rows,
+            self.cols
+        );
+        &mut self.data[i * self.row_stride + j * self.col_stride]
+    }
+}
+
+fn check_shape<T>(
+    op: &'static str,
+    rows: usize,
+    cols: usize,
+    other: &MatrixView<T>,
+) -> Result<(), MatrixError> {
+    if rows != other.rows || cols != other.cols {
+        return Err(MatrixError::DimensionMismatch {
+            op,
+            lhs: (rows, cols),
+            rhs: (other.rows, other.cols),
+        });
+    }
+    Ok(())
+}
+
+/// `out = a + b`.
+pub fn add<T: Scalar>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    out: &mut MatrixViewMut<T>,
+) -> Result<(), MatrixError> {
+    elementwise("add", a, b, out, T::add_slices, |x, y| x + y)
+}
+
+/// `out = a - b`.
+pub fn subtract<T: Scalar>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    out: &mut MatrixViewMut<T>,
+) -> Result<(), MatrixError> {
+    elementwise("subtract", a, b, out, T::sub_slices, |x, y| x - y)
+}
+
+/// Row by row through the `Scalar` slice kernels when every view has
+/// contiguous rows, element by element otherwise.
+fn elementwise<T: Scalar>(
+    op: &'static str,
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    out: &mut MatrixViewMut<T>,
+    slices: fn(&[T], &[T], &mut [T]),
+    scalar: fn(T, T) -> T,
+) -> Result<(), MatrixError> {
+    check_shape(op, a.rows, a.cols, b)?;
+    check_shape(op, out.rows, out.cols, a)?;
+
+    for i in 0..a.rows {
+        match (a.row_slice(i), b.row_slice(i)) {
+            (Some(x), Some(y)) if out.col_stride == 1 => {
+                let start = i * out.row_stride;
+                slices(x, y, &mut out.data[start..start + out.cols]);
+            }
+            _ => {
+                for j in 0..a.cols {
+                    out[(i, j)] = scalar(a[(i, j)], b[(i, j)]);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `c += a * b`. With contiguous rows in all three views this is the packed
+/// `gemm` reading the blocks in place; otherwise an `i-k-j` loop over the
+/// strides.
+pub fn multiply_add<T: Scalar>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    c: &mut MatrixViewMut<T>,
+) -> Result<(), MatrixError> {
+    if a.cols != b.rows {
+        return Err(MatrixError::DimensionMismatch {
+            op: "multiply",
+            lhs: (a.rows, a.cols),
+            rhs: (b.rows, b.cols),
+        });
+    }
+    if c.rows != a.rows || c.cols != b.cols {
+        return Err(MatrixError::DimensionMismatch {
+            op: "multiply into",
+            lhs: (a.rows, b.cols),
+            rhs: (c.rows, c.cols),
+        });
+    }
+    if a.rows == 0 || b.cols == 0 || a.cols == 0 {
+        return Ok(());
+    }
+
+    if a.col_stride == 1 && b.col_stride == 1 && c.col_stride == 1 {
+        gemm::gemm(
+            a.rows,
+            b.cols,
+            a.cols,
+            a.data,
+            a.row_stride,
+            b.data,
+            b.row_stride,
+            c.data,
+            c.row_stride,
+        );
+        return Ok(());
+    }
+
+    for i in 0..a.rows {
+        for k in 0..a.cols {
+            let a_ik = a[(i, k)];
+            for j in 0..b.cols {
+                c[(i, j)] += a_ik * b[(k, j)];
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+// Leitura e escrita de `Matrix` em arquivos, usando os formatos de
+// `matrix_core::io` (Matrix Market, CSV e o binário próprio). Os formatos
+// trabalham com buffers row-major, então as linhas são juntadas ou separadas
+// na borda.
+
+use std::fmt::LowerExp;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use matrix_core::io::binary::{self, BinaryElement, Layout};
+use matrix_core::io::csv::{self, CsvFormat};
+use matrix_core::io::market::{self, MarketHeader};
+use matrix_core::io::FormatError;
+
+use crate::{Matrix, Scalar};
+
+impl<T: Scalar> Matrix<T> {
+    fn from_row_major(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
+        Matrix {
+            rows,
+            cols,
+            data: data.chunks(cols.max(1)).map(<[T]>::to_vec).collect(),
+        }
+    }
+
+    fn to_row_major(&self) -> Vec<T> {
+        self.data.concat()
+    }
+}
+
+impl<T: Scalar + FromStr> Matrix<T> {
+    /// Reads a Matrix Market file, coordinate or array. Sparse files are
+    /// stored dense, so mind the size of SuiteSparse inputs.
+    pub fn from_matrix_market<R: BufRead>(reader: R) -> Result<Matrix<T>, FormatError> {
+        let (rows, cols, data) = market::read_dense(reader)?;
+        Ok(Matrix::from_row_major(rows, cols, data))
+    }
+
+    pub fn read_matrix_market<P: AsRef<Path>>(path: P) -> Result<Matrix<T>, FormatError> {
+        Matrix::from_matrix_market(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a delimited text file, one line per row. With
+    /// `format.has_header` the first line is skipped.
+    pub fn from_csv_reader<R: BufRead>(
+        reader: R,
+        format: CsvFormat,
+    ) -> Result<Matrix<T>, FormatError> {
+        let table = csv::read(reader, format)?;
+        Ok(Matrix::from_row_major(table.rows, table.cols, table.data))
+    }
+
+    pub fn read_csv<P: AsRef<Path>>(path: P, format: CsvFormat) -> Result<Matrix<T>, FormatError> {
+        Matrix::from_csv_reader(BufReader::new(File::open(path)?), format)
+    }
+}
+
+impl<T: Scalar> Matrix<T> {
+    pub fn to_csv_writer<W: Write>(&self, writer: W, format: CsvFormat) -> Result<(), FormatError> {
+        csv::write(writer, format, self.rows, self.cols, &self.to_row_major())
+    }
+
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P, format: CsvFormat) -> Result<(), FormatError> {
+        self.to_csv_writer(BufWriter::new(File::create(path)?), format)
+    }
+}
+
+impl<T: Scalar + LowerExp> Matrix<T> {
+    pub fn to_matrix_market<W: Write>(
+        &self,
+        writer: W,
+        header: MarketHeader,
+    ) -> Result<(), FormatError> {
+        market::write_dense(writer, header, self.rows, self.cols, &self.to_row_major())
+    }
+
+    pub fn write_matrix_market<P: AsRef<Path>>(
+        &self,
+        path: P,
+        header: MarketHeader,
+    ) -> Result<(), FormatError> {
+        self.to_matrix_market(BufWriter::new(File::create(path)?), header)
+    }
+}
+
+impl<T: BinaryElement> Matrix<T> {
+    /// Reads the binary format of `matrix_core::io::binary`, checking the
+    /// checksum. Column-major files come back row-major.
+    pub fn from_binary<R: Read>(reader: R) -> Result<Matrix<T>, FormatError> {
+        let (rows, cols, data) = binary::read(reader)?;
+        Ok(Matrix::from_row_major(rows, cols, data))
+    }
+
+    pub fn read_binary<P: AsRef<Path>>(path: P) -> Result<Matrix<T>, FormatError> {
+        Matrix::from_binary(BufReader::new(File::open(path)?))
+    }
+
+    pub fn to_binary<W: Write>(&self, writer: W) -> Result<(), FormatError> {
+        binary::write(
+            writer,
+            Layout::RowMajor,
+            self.rows,
+            self.cols,
+            &self.to_row_major(),
+        )
+    }
+
+    pub fn write_binary<P: AsRef<Path>>(&self, path: P) -> Result<(), FormatError> {
+        self.to_binary(BufWriter::new(File::create(path)?))
+    }
+}
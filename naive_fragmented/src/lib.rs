@@ -1,22 +1,32 @@
-use rand::Rng;
+#![allow(clippy::needless_range_loop)]
+
+mod io;
+#[allow(clippy::module_inception)]
 mod linear_algebra_tests;
+mod operators;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+pub use matrix_core::io::binary::BinaryElement;
+pub use matrix_core::io::csv::CsvFormat;
+pub use matrix_core::io::market::{MarketField, MarketFormat, MarketHeader, MarketSymmetry};
+pub use matrix_core::io::FormatError;
+pub use matrix_core::{MatrixError, MatrixOps, Scalar};
+
 #[derive(Clone, Serialize, Deserialize)]
-pub struct Matrix {
+pub struct Matrix<T = f64> {
     pub rows: usize,
     pub cols: usize,
-    pub data: Vec<Vec<f64>>,
+    pub data: Vec<Vec<T>>,
 }
 
-impl Matrix {
-    pub fn random(rows: usize, cols: usize) -> Matrix {
-        let mut buffer = vec![vec![0.0; cols]; rows];
+impl<T: Scalar> Matrix<T> {
+    pub fn random(rows: usize, cols: usize) -> Matrix<T> {
+        let mut buffer = vec![vec![T::zero(); cols]; rows];
 
         for i in 0..rows {
             for j in 0..cols {
-                buffer[i][j] = rand::thread_rng().gen::<f64>() * 2.0 - 1.0;
+                buffer[i][j] = T::random();
             }
         }
 
@@ -27,28 +37,50 @@ impl Matrix {
         }
     }
 
-    pub fn new(rows: usize, cols: usize, data: Vec<Vec<f64>>) -> Matrix {
-        assert!(data.len() - 1 != rows * cols, "Invalid Size");
-        Matrix { rows, cols, data }
+    pub fn new(rows: usize, cols: usize, data: Vec<Vec<T>>) -> Matrix<T> {
+        Matrix::try_new(rows, cols, data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(rows: usize, cols: usize, data: Vec<Vec<T>>) -> Result<Matrix<T>, MatrixError> {
+        if data.len() != rows {
+            return Err(MatrixError::InvalidDataLength {
+                expected: rows,
+                actual: data.len(),
+            });
+        }
+
+        if let Some(row) = data.iter().find(|row| row.len() != cols) {
+            return Err(MatrixError::InvalidDataLength {
+                expected: cols,
+                actual: row.len(),
+            });
+        }
+
+        Ok(Matrix { rows, cols, data })
     }
 
-    pub fn zeros(rows: usize, cols: usize) -> Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
         Matrix {
             rows,
             cols,
-            data: vec![vec![0.0; cols]; rows],
+            data: vec![vec![T::zero(); cols]; rows],
         }
     }
 
-    pub fn add(&self, other: &Matrix) -> Matrix {
+    pub fn add(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.rows != other.rows || self.cols != other.cols {
-            panic!(
-                "Cannot add matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![vec![0.0; self.cols]; self.rows];
+        let mut result_data = vec![vec![T::zero(); self.cols]; self.rows];
 
         for i in 0..self.rows {
             for j in 0..self.cols {
@@ -56,20 +88,27 @@ impl Matrix {
             }
         }
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
     }
 
-    pub fn subtract(&self, other: &Matrix) -> Matrix {
-        assert!(
-            self.rows == other.rows && self.cols == other.cols,
-            "Cannot subtract matrices with different dimensions"
-        );
+    pub fn subtract(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_subtract(other).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        let mut result_data = vec![vec![0.0; self.cols]; self.rows];
+    pub fn try_subtract(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MatrixError::DimensionMismatch {
+                op: "subtract",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
+        }
+
+        let mut result_data = vec![vec![T::zero(); self.cols]; self.rows];
 
         for i in 0..self.rows {
             for j in 0..self.cols {
@@ -77,26 +116,31 @@ impl Matrix {
             }
         }
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
+    }
+
+    pub fn multiply(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_multiply(other).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    pub fn multiply(&self, other: &Matrix) -> Matrix {
+    pub fn try_multiply(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.cols != other.rows {
-            panic!(
-                "Cannot multiply matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![vec![0.0; other.cols]; self.rows];
+        let mut result_data = vec![vec![T::zero(); other.cols]; self.rows];
 
         for i in 0..self.rows {
             for j in 0..other.cols {
-                let mut sum = 0.0;
+                let mut sum = T::zero();
                 for k in 0..self.cols {
                     sum += self.data[i][k] * other.data[k][j];
                 }
@@ -104,22 +148,28 @@ impl Matrix {
             }
         }
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: other.cols,
             data: result_data,
-        }
+        })
     }
 
-    pub fn hadamard_product(&self, other: &Matrix) -> Matrix {
+    pub fn hadamard_product(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.try_hadamard_product(other)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_hadamard_product(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
         if self.rows != other.rows || self.cols != other.cols {
-            panic!(
-                "Cannot apply hadamard product to matrices. {}x{} & {}x{}",
-                self.rows, self.cols, other.rows, other.cols
-            )
+            return Err(MatrixError::DimensionMismatch {
+                op: "hadamard product",
+                lhs: (self.rows, self.cols),
+                rhs: (other.rows, other.cols),
+            });
         }
 
-        let mut result_data = vec![vec![0.0; self.cols]; self.rows];
+        let mut result_data = vec![vec![T::zero(); self.cols]; self.rows];
 
         for i in 0..self.rows {
             for j in 0..self.cols {
@@ -127,15 +177,15 @@ impl Matrix {
             }
         }
 
-        Matrix {
+        Ok(Matrix {
             rows: self.rows,
             cols: self.cols,
             data: result_data,
-        }
+        })
     }
 
-    pub fn transpose(&self) -> Matrix {
-        let mut buffer = vec![vec![0.0; self.rows]; self.cols];
+    pub fn transpose(&self) -> Matrix<T> {
+        let mut buffer = vec![vec![T::zero(); self.rows]; self.cols];
 
         for i in 0..self.rows {
             for j in 0..self.cols {
@@ -148,13 +198,13 @@ impl Matrix {
             data: buffer,
         }
     }
-    pub fn identity(size: usize) -> Matrix {
-        let mut result_data = vec![vec![0.0; size]; size];
+    pub fn identity(size: usize) -> Matrix<T> {
+        let mut result_data = vec![vec![T::zero(); size]; size];
 
         for i in 0..size {
             for j in 0..size {
                 if j.eq(&i) {
-                    result_data[i][j] = 1.0;
+                    result_data[i][j] = T::one();
                 }
             }
         }
@@ -166,22 +216,99 @@ impl Matrix {
         }
     }
 
-    pub fn apply_function(&self, func: &dyn Fn(f64) -> f64) -> Matrix {
-        let a: Vec<Vec<f64>> = self.data.iter().map(|row| row.iter().map(|&val| func(val)).collect()).collect();
-        return Matrix {
+    pub fn apply_function(&self, func: &dyn Fn(T) -> T) -> Matrix<T> {
+        let a: Vec<Vec<T>> = self
+            .data
+            .iter()
+            .map(|row| row.iter().map(|&val| func(val)).collect())
+            .collect();
+        Matrix {
             cols: self.cols,
             rows: self.rows,
             data: a,
-        };
+        }
+    }
+}
+
+impl<T: Scalar> MatrixOps for Matrix<T> {
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.data[i][j]
+    }
+
+    fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::zeros(rows, cols)
+    }
+
+    fn random(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::random(rows, cols)
+    }
+
+    fn identity(size: usize) -> Matrix<T> {
+        Matrix::identity(size)
+    }
+
+    fn add(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.add(other)
+    }
+
+    fn try_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_add(other)
+    }
+
+    fn subtract(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.subtract(other)
+    }
+
+    fn try_subtract(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_subtract(other)
+    }
+
+    fn multiply(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.multiply(other)
+    }
+
+    fn try_multiply(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_multiply(other)
+    }
+
+    fn hadamard_product(&self, other: &Matrix<T>) -> Matrix<T> {
+        self.hadamard_product(other)
+    }
+
+    fn try_hadamard_product(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        self.try_hadamard_product(other)
+    }
+
+    fn transpose(&self) -> Matrix<T> {
+        self.transpose()
+    }
+
+    fn apply_function(&self, func: &dyn Fn(T) -> T) -> Matrix<T> {
+        self.apply_function(func)
     }
 }
 
-impl fmt::Display for Matrix {
+impl<T: Scalar> fmt::Display for Matrix<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let max_width = self
             .data
             .iter()
-            .map(|f| f.iter().map(|&val| val.to_string().len()).max().unwrap_or(0))
+            .map(|f| {
+                f.iter()
+                    .map(|&val| val.to_string().len())
+                    .max()
+                    .unwrap_or(0)
+            })
             .max()
             .unwrap_or(0);
 
@@ -189,11 +316,7 @@ impl fmt::Display for Matrix {
             write!(f, "|")?;
             for j in 0..self.cols {
                 // std::fmt fill/alignment
-                let cell_str = format!(
-                    "{:^width$}",
-                    self.data[i][j],
-                    width = max_width
-                );
+                let cell_str = format!("{:^width$}", self.data[i][j], width = max_width);
                 write!(f, "{}", cell_str)?;
                 if j < self.cols - 1 {
                     // Print a whitespace between values in the same row
@@ -206,19 +329,15 @@ impl fmt::Display for Matrix {
     }
 }
 
-impl From<Vec<f64>> for Matrix {
+impl<T: Scalar> From<Vec<T>> for Matrix<T> {
     /// This method will always return a matrix with rows = vec.len() and cols = 1
-    fn from(vec: Vec<f64>) -> Self {
+    fn from(vec: Vec<T>) -> Self {
         let rows = vec.len();
         let cols = 1;
         let mut data = vec![];
         for i in 0..rows {
             data.push(vec![vec[i]; cols]);
         }
-        Matrix {
-            rows,
-            cols,
-            data,
-        }
+        Matrix { rows, cols, data }
     }
 }
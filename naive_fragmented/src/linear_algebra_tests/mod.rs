@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod linear_algebra_tests {
-    use crate::Matrix;
+    use crate::{
+        CsvFormat, FormatError, MarketField, MarketHeader, Matrix, MatrixError, MatrixOps,
+    };
 
     #[test]
     fn matrix_creation() {
@@ -15,6 +17,52 @@ mod linear_algebra_tests {
         assert_eq!(matrix.data[1][1], 2.5);
     }
 
+    #[test]
+    fn try_new_should_reject_invalid_data_length() {
+        assert_eq!(
+            Matrix::try_new(2, 2, vec![vec![1.0, 1.5], vec![2.0]]).err(),
+            Some(MatrixError::InvalidDataLength {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            Matrix::<f64>::try_new(2, 2, vec![]).err(),
+            Some(MatrixError::InvalidDataLength {
+                expected: 2,
+                actual: 0
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Size")]
+    fn matrix_creation_should_fail_when_data_length_does_not_match() {
+        Matrix::new(3, 3, vec![vec![1.0], vec![4.0]]);
+    }
+
+    #[test]
+    fn try_operations_should_return_dimension_mismatch() {
+        let matrix_a: Matrix = Matrix::new(2, 1, vec![vec![1.0], vec![4.0]]);
+        let matrix_b: Matrix = Matrix::new(2, 2, vec![vec![3.0, 5.0], vec![5.0, 7.0]]);
+
+        assert_eq!(
+            matrix_a.try_add(&matrix_b).err(),
+            Some(MatrixError::DimensionMismatch {
+                op: "add",
+                lhs: (2, 1),
+                rhs: (2, 2)
+            })
+        );
+        assert!(matrix_a.try_subtract(&matrix_b).is_err());
+        assert!(matrix_a.try_hadamard_product(&matrix_b).is_err());
+        assert!(matrix_a.try_multiply(&matrix_b).is_err());
+
+        let multiplied_matrix: Matrix = matrix_b.try_multiply(&matrix_a).unwrap();
+        assert_eq!(multiplied_matrix.rows, 2);
+        assert_eq!(multiplied_matrix.cols, 1);
+    }
+
     #[test]
     fn matrix_of_zeros() {
         let matrix: Matrix = Matrix::zeros(2, 2);
@@ -30,7 +78,7 @@ mod linear_algebra_tests {
 
     #[test]
     fn random_matrix() {
-        let matrix = Matrix::random(10, 10);
+        let matrix: Matrix = Matrix::random(10, 10);
 
         for i in 0..matrix.rows {
             assert_ne!(matrix.data[i][0], 0.0);
@@ -43,7 +91,7 @@ mod linear_algebra_tests {
     #[test]
     #[should_panic]
     fn matrix_multiplication_should_fail_when_matrix_a_rows_does_not_match_matrix_b_cols() {
-        let a = vec![vec![1.0, 10.0], vec![4.0, 4.0], vec![9.0, 7.0]];
+        let a = vec![vec![1.0, 10.0, 4.0], vec![4.0, 9.0, 7.0]];
         let b = vec![vec![3.0, 5.0], vec![5.0, 7.0]];
         let matrix_a: Matrix = Matrix::new(2, 3, a);
         let matrix_b: Matrix = Matrix::new(2, 2, b);
@@ -83,10 +131,36 @@ mod linear_algebra_tests {
         assert_eq!(multiplied_matrix.data[1][0], 83.0);
     }
 
+    #[test]
+    fn f32_matrix_2x2_multiplication() {
+        let matrix_a: Matrix<f32> = Matrix::new(2, 2, vec![vec![1.0, 10.0], vec![4.0, 9.0]]);
+        let matrix_b: Matrix<f32> = Matrix::new(2, 2, vec![vec![3.0, 5.0], vec![5.0, 7.0]]);
+        let multiplied_matrix: Matrix<f32> = matrix_a.multiply(&matrix_b);
+
+        assert_eq!(multiplied_matrix.data[0][0], 53.0f32);
+        assert_eq!(multiplied_matrix.data[0][1], 75.0f32);
+        assert_eq!(multiplied_matrix.data[1][0], 57.0f32);
+        assert_eq!(multiplied_matrix.data[1][1], 83.0f32);
+    }
+
+    #[test]
+    fn integer_matrix_operations() {
+        let matrix_a: Matrix<i32> = Matrix::new(2, 2, vec![vec![1, 10], vec![4, 9]]);
+        let matrix_b: Matrix<i32> = Matrix::new(2, 2, vec![vec![3, 5], vec![5, 7]]);
+        let multiplied_matrix = matrix_a.multiply(&matrix_b);
+        let subtracted_matrix = matrix_a.subtract(&matrix_b);
+
+        assert_eq!(multiplied_matrix.data[0][0], 53);
+        assert_eq!(multiplied_matrix.data[1][1], 83);
+        assert_eq!(subtracted_matrix.data[0][0], -2);
+        assert_eq!(subtracted_matrix.data[1][1], 2);
+        assert_eq!(Matrix::<u64>::identity(2).data[1][1], 1);
+    }
+
     #[test]
     #[should_panic]
     fn matrix_addition_should_fail_when_matrices_are_not_same_dimensions() {
-        let a = vec![vec![1.0, 4.0]];
+        let a = vec![vec![1.0], vec![4.0]];
         let b = vec![vec![3.0, 5.0], vec![5.0, 7.0]];
         let matrix_a: Matrix = Matrix::new(2, 1, a);
         let matrix_b: Matrix = Matrix::new(2, 2, b);
@@ -129,7 +203,7 @@ mod linear_algebra_tests {
     #[test]
     #[should_panic]
     fn matrix_subtraction_should_fail_when_matrices_are_not_same_dimensions() {
-        let a = vec![vec![1.0, 4.0]];
+        let a = vec![vec![1.0], vec![4.0]];
         let b = vec![vec![3.0, 5.0], vec![5.0, 7.0]];
         let matrix_a: Matrix = Matrix::new(2, 1, a);
         let matrix_b: Matrix = Matrix::new(2, 2, b);
@@ -172,7 +246,7 @@ mod linear_algebra_tests {
     #[test]
     #[should_panic]
     fn hadamard_product_should_fail_when_matrices_are_not_same_dimensions() {
-        let a = vec![vec![1.0, 4.0]];
+        let a = vec![vec![1.0], vec![4.0]];
         let b = vec![vec![3.0, 5.0], vec![5.0, 7.0]];
         let matrix_a: Matrix = Matrix::new(2, 1, a);
         let matrix_b: Matrix = Matrix::new(2, 2, b);
@@ -250,12 +324,11 @@ mod linear_algebra_tests {
         assert_eq!(matrix_a.data[2][2], 1.0);
     }
 
-
     #[test]
     fn apply_function_to_matrix() {
         let a = vec![vec![1.0, 10.0], vec![4.0, 9.0]];
         let matrix_a: Matrix = Matrix::new(2, 2, a);
-        let multiplied_by_2_matrix: Matrix = matrix_a.apply_function(&|x| (x * 2.0));
+        let multiplied_by_2_matrix: Matrix = matrix_a.apply_function(&|x| x * 2.0);
 
         assert_eq!(multiplied_by_2_matrix.rows, 2);
         assert_eq!(multiplied_by_2_matrix.cols, 2);
@@ -265,4 +338,174 @@ mod linear_algebra_tests {
         assert_eq!(multiplied_by_2_matrix.data[1][0], 8.0);
         assert_eq!(multiplied_by_2_matrix.data[1][1], 18.0);
     }
+
+    fn multiply_add_identity_transpose<M: MatrixOps>(a: &M, b: &M) -> M {
+        a.multiply(b).add(&M::identity(a.rows())).transpose()
+    }
+
+    #[test]
+    fn matrix_ops_trait() {
+        let matrix_a: Matrix = Matrix::new(2, 2, vec![vec![1.0, 10.0], vec![4.0, 9.0]]);
+        let matrix_b: Matrix = Matrix::new(2, 2, vec![vec![3.0, 5.0], vec![5.0, 7.0]]);
+        let result: Matrix = multiply_add_identity_transpose(&matrix_a, &matrix_b);
+
+        assert_eq!(MatrixOps::rows(&result), 2);
+        assert_eq!(MatrixOps::cols(&result), 2);
+
+        assert_eq!(result.get(0, 0), 54.0);
+        assert_eq!(result.get(0, 1), 57.0);
+        assert_eq!(result.get(1, 0), 75.0);
+        assert_eq!(result.get(1, 1), 84.0);
+    }
+
+    #[test]
+    fn operators_match_the_methods() {
+        let a: Matrix<i64> = Matrix::random(6, 6);
+        let b: Matrix<i64> = Matrix::random(6, 6);
+        let sum = a.add(&b);
+        let difference = a.subtract(&b);
+        let product = a.multiply(&b);
+
+        assert_eq!((&a + &b).data, sum.data);
+        assert_eq!((a.clone() + &b).data, sum.data);
+        assert_eq!((&a + b.clone()).data, sum.data);
+        assert_eq!((a.clone() + b.clone()).data, sum.data);
+
+        assert_eq!((&a - &b).data, difference.data);
+        assert_eq!((a.clone() - &b).data, difference.data);
+        assert_eq!((&a - b.clone()).data, difference.data);
+        assert_eq!((a.clone() - b.clone()).data, difference.data);
+
+        assert_eq!((&a * &b).data, product.data);
+        assert_eq!((a.clone() * &b).data, product.data);
+        assert_eq!((&a * b.clone()).data, product.data);
+        assert_eq!((a.clone() * b.clone()).data, product.data);
+
+        let doubled = a.apply_function(&|x| 2 * x);
+        assert_eq!((&a * 2).data, doubled.data);
+        assert_eq!((a.clone() * 2).data, doubled.data);
+        assert_eq!((2 * &a).data, doubled.data);
+        assert_eq!((-&a).data, a.apply_function(&|x| -x).data);
+        assert_eq!((-(-a.clone())).data, a.data);
+
+        let mut c = a.clone();
+        c += &b;
+        assert_eq!(c.data, sum.data);
+        c -= b.clone();
+        assert_eq!(c.data, a.data);
+        c *= &b;
+        assert_eq!(c.data, product.data);
+        c *= 3;
+        assert_eq!(c.data, product.apply_function(&|x| 3 * x).data);
+
+        let x: Matrix = Matrix::random(3, 4);
+        assert_eq!((0.5 * &x).data, (&x * 0.5).data);
+    }
+
+    #[test]
+    fn index_reads_and_writes_elements() {
+        let mut matrix: Matrix = Matrix::zeros(2, 3);
+        matrix[(1, 2)] = 4.5;
+        matrix[(0, 1)] += 1.0;
+
+        assert_eq!(matrix[(1, 2)], 4.5);
+        assert_eq!(matrix[(0, 1)], 1.0);
+        assert_eq!(matrix[(1, 2)], MatrixOps::get(&matrix, 1, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_rejects_a_column_past_the_end() {
+        let matrix: Matrix = Matrix::zeros(2, 3);
+        let _ = matrix[(0, 3)];
+    }
+
+    #[test]
+    #[should_panic(expected = "Dimension mismatch in add. 2x2 & 3x3")]
+    fn operators_panic_on_dimension_mismatch() {
+        let _ = &Matrix::<f64>::zeros(2, 2) + &Matrix::zeros(3, 3);
+    }
+
+    #[test]
+    fn matrix_market_round_trip() {
+        let a: Matrix<i64> = Matrix::random(5, 4);
+        let header = MarketHeader {
+            field: MarketField::Integer,
+            ..MarketHeader::coordinate()
+        };
+        let mut file = Vec::new();
+        a.to_matrix_market(&mut file, header).unwrap();
+        assert_eq!(
+            Matrix::<i64>::from_matrix_market(file.as_slice())
+                .unwrap()
+                .data,
+            a.data
+        );
+
+        let path = std::env::temp_dir().join(format!("{}_round_trip.mtx", env!("CARGO_PKG_NAME")));
+        let b: Matrix = Matrix::random(3, 6);
+        b.write_matrix_market(&path, MarketHeader::array()).unwrap();
+        let read = Matrix::<f64>::read_matrix_market(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((read.rows, read.cols), (3, 6));
+        assert_eq!(read.data, b.data);
+
+        let truncated = "%%MatrixMarket matrix array real general\n2 2\n1\n";
+        assert!(matches!(
+            Matrix::<f64>::from_matrix_market(truncated.as_bytes()),
+            Err(FormatError::Parse { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn csv_round_trip_and_cell_errors() {
+        let a: Matrix = Matrix::random(4, 3);
+        let format = CsvFormat {
+            has_header: true,
+            ..CsvFormat::decimal_comma()
+        };
+        let mut file = Vec::new();
+        a.to_csv_writer(&mut file, format).unwrap();
+        let read = Matrix::<f64>::from_csv_reader(file.as_slice(), format).unwrap();
+        assert_eq!((read.rows, read.cols), (4, 3));
+        assert_eq!(read.data, a.data);
+
+        let path = std::env::temp_dir().join(format!("{}_round_trip.csv", env!("CARGO_PKG_NAME")));
+        a.write_csv(&path, CsvFormat::default()).unwrap();
+        let read = Matrix::<f64>::read_csv(&path, CsvFormat::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.data, a.data);
+
+        let b =
+            Matrix::<i64>::from_csv_reader("1;2\n3;4.000\n".as_bytes(), CsvFormat::decimal_comma())
+                .unwrap();
+        assert_eq!(b[(1, 1)], 4000);
+
+        let bad = "1,2\n3,4\n5,six\n";
+        assert!(matches!(
+            Matrix::<f64>::from_csv_reader(bad.as_bytes(), CsvFormat::default()),
+            Err(FormatError::Cell { row: 3, col: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let a: Matrix = Matrix::random(5, 3);
+        let mut file = Vec::new();
+        a.to_binary(&mut file).unwrap();
+        let read = Matrix::<f64>::from_binary(file.as_slice()).unwrap();
+        assert_eq!((read.rows, read.cols), (5, 3));
+        assert_eq!(read.data, a.data);
+
+        let path = std::env::temp_dir().join(format!("{}_round_trip.bin", env!("CARGO_PKG_NAME")));
+        a.write_binary(&path).unwrap();
+        let read = Matrix::<f64>::read_binary(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.data, a.data);
+
+        assert!(matches!(
+            Matrix::<i64>::from_binary(file.as_slice()),
+            Err(FormatError::InvalidHeader(_))
+        ));
+    }
 }
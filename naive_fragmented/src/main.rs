@@ -1,12 +1,48 @@
 use std::env;
+use std::str::FromStr;
 
-use naive_fragmented::Matrix;
+use matrix_core::io::binary::MappedMatrix;
+
+use naive_fragmented::{BinaryElement, Matrix};
 
 fn main() {
     let size = env::var("SIZE").unwrap_or_else(|_| "1000".to_string());
+    let dtype = env::var("DTYPE").unwrap_or_else(|_| "f64".to_string());
     let size = size.parse::<usize>().unwrap();
 
-    let matrix_a = Matrix::random(size, size);
-    let matrix_b = Matrix::random(size, size);
+    match dtype.as_str() {
+        "f32" => run::<f32>(size),
+        "f64" => run::<f64>(size),
+        other => panic!("DTYPE desconhecido: {} (use f32 ou f64)", other),
+    }
+}
+
+fn run<T: BinaryElement + FromStr>(size: usize) {
+    let matrix_a = input::<T>("INPUT_A", size, size);
+    let matrix_b = input::<T>("INPUT_B", matrix_a.cols, matrix_a.cols);
     matrix_a.multiply(&matrix_b);
 }
+
+// INPUT_A/INPUT_B apontam para arquivos Matrix Market (.mtx), como os da
+// SuiteSparse, ou no formato binário de `matrix_core::io::binary` (qualquer
+// outra extensão, gerados com `generate_input`); sem elas as matrizes são
+// aleatórias. B aleatória tem o número de colunas de A nos dois lados.
+fn input<T: BinaryElement + FromStr>(var: &str, rows: usize, cols: usize) -> Matrix<T> {
+    let path = match env::var(var) {
+        Ok(path) => path,
+        Err(_) => return Matrix::random(rows, cols),
+    };
+    let matrix = if path.ends_with(".mtx") {
+        Matrix::read_matrix_market(&path)
+    } else {
+        // o arquivo é mapeado em vez de lido; as linhas do `Vec<Vec<T>>`
+        // são copiadas da view, que já entrega um arquivo por coluna
+        // row-major
+        MappedMatrix::open(&path).map(|mapped| {
+            let view = mapped.view();
+            let data = (0..view.rows()).map(|i| view.row(i).to_vec()).collect();
+            Matrix::new(view.rows(), view.cols(), data)
+        })
+    };
+    matrix.unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
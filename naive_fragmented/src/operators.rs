@@ -0,0 +1,224 @@
+// Operadores do `std::ops` para `Matrix`. Todos chamam os métodos do
+// `MatrixOps`, com os loops sobre `Vec<Vec<T>>` deste crate. Os crates
+// contíguos geram os seus com `matrix_core::impl_contiguous_operators`, que
+// escreve no buffer do lado esquerdo quando ele vem por valor; aqui não há
+// versões in-place, então as variantes com valores só chamam as de
+// referência. Dimensões incompatíveis causam panic, como nos métodos sem
+// `try_`.
+
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Matrix, MatrixOps, Scalar};
+
+impl<T: Scalar> Add<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: &Matrix<T>) -> Matrix<T> {
+        MatrixOps::add(self, rhs)
+    }
+}
+
+impl<T: Scalar> Add<&Matrix<T>> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: &Matrix<T>) -> Matrix<T> {
+        &self + rhs
+    }
+}
+
+impl<T: Scalar> Add<Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: Matrix<T>) -> Matrix<T> {
+        self + &rhs
+    }
+}
+
+impl<T: Scalar> Add<Matrix<T>> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, rhs: Matrix<T>) -> Matrix<T> {
+        &self + &rhs
+    }
+}
+
+impl<T: Scalar> Sub<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: &Matrix<T>) -> Matrix<T> {
+        MatrixOps::subtract(self, rhs)
+    }
+}
+
+impl<T: Scalar> Sub<&Matrix<T>> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: &Matrix<T>) -> Matrix<T> {
+        &self - rhs
+    }
+}
+
+impl<T: Scalar> Sub<Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: Matrix<T>) -> Matrix<T> {
+        self - &rhs
+    }
+}
+
+impl<T: Scalar> Sub<Matrix<T>> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, rhs: Matrix<T>) -> Matrix<T> {
+        self - &rhs
+    }
+}
+
+/// Matrix product.
+impl<T: Scalar> Mul<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Matrix<T>) -> Matrix<T> {
+        MatrixOps::multiply(self, rhs)
+    }
+}
+
+impl<T: Scalar> Mul<&Matrix<T>> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: &Matrix<T>) -> Matrix<T> {
+        &self * rhs
+    }
+}
+
+impl<T: Scalar> Mul<Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Matrix<T>) -> Matrix<T> {
+        self * &rhs
+    }
+}
+
+impl<T: Scalar> Mul<Matrix<T>> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Matrix<T>) -> Matrix<T> {
+        &self * &rhs
+    }
+}
+
+/// Every element times a scalar.
+impl<T: Scalar> Mul<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: T) -> Matrix<T> {
+        self.apply_function(&|x| x * rhs)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: T) -> Matrix<T> {
+        &self * rhs
+    }
+}
+
+// escalar à esquerda (`2.0 * &a`) só dá para implementar tipo a tipo
+macro_rules! impl_scalar_lhs_mul {
+    ($($t:ty),*) => {
+        $(
+            impl Mul<&Matrix<$t>> for $t {
+                type Output = Matrix<$t>;
+
+                fn mul(self, rhs: &Matrix<$t>) -> Matrix<$t> {
+                    rhs * self
+                }
+            }
+
+            impl Mul<Matrix<$t>> for $t {
+                type Output = Matrix<$t>;
+
+                fn mul(self, rhs: Matrix<$t>) -> Matrix<$t> {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_lhs_mul!(f32, f64, i32, i64, u32, u64);
+
+impl<T: Scalar + Neg<Output = T>> Neg for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Matrix<T> {
+        self.apply_function(&|x| -x)
+    }
+}
+
+impl<T: Scalar + Neg<Output = T>> Neg for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn neg(self) -> Matrix<T> {
+        -&self
+    }
+}
+
+impl<T: Scalar> AddAssign<&Matrix<T>> for Matrix<T> {
+    fn add_assign(&mut self, rhs: &Matrix<T>) {
+        *self = &*self + rhs;
+    }
+}
+
+impl<T: Scalar> AddAssign<Matrix<T>> for Matrix<T> {
+    fn add_assign(&mut self, rhs: Matrix<T>) {
+        *self += &rhs;
+    }
+}
+
+impl<T: Scalar> SubAssign<&Matrix<T>> for Matrix<T> {
+    fn sub_assign(&mut self, rhs: &Matrix<T>) {
+        *self = &*self - rhs;
+    }
+}
+
+impl<T: Scalar> SubAssign<Matrix<T>> for Matrix<T> {
+    fn sub_assign(&mut self, rhs: Matrix<T>) {
+        *self -= &rhs;
+    }
+}
+
+/// `a *= &b` is `a = &a * &b`; the product needs a new buffer.
+impl<T: Scalar> MulAssign<&Matrix<T>> for Matrix<T> {
+    fn mul_assign(&mut self, rhs: &Matrix<T>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<T: Scalar> MulAssign<Matrix<T>> for Matrix<T> {
+    fn mul_assign(&mut self, rhs: Matrix<T>) {
+        *self *= &rhs;
+    }
+}
+
+impl<T: Scalar> MulAssign<T> for Matrix<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = &*self * rhs;
+    }
+}
+
+/// `a[(i, j)]`.
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i][j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i][j]
+    }
+}
@@ -5,7 +5,11 @@ N_WARMUP=10
 N_REPETITIONS=50
 SIZE="1000"
 BLOCK_SIZE="264"
-OUTPUT_FILE="test_results-$N_REPETITIONS-$SIZE-$BLOCK_SIZE.txt"
+# tamanho abaixo do qual o Strassen usa o kernel tiled
+CUTOFF="64"
+# f64 ou f32
+DTYPE="f64"
+OUTPUT_FILE="test_results-$N_REPETITIONS-$SIZE-$BLOCK_SIZE-$DTYPE.txt"
 
 > $OUTPUT_FILE
 
@@ -16,11 +20,16 @@ PERF_EVENTS="task-clock,cache-misses,branch-misses,instructions"
 
 
 PROGRAMS=(
-    "./target/release/naive_fragmented|SIZE=\"$SIZE\"|naive_fragmented"
-    "./target/release/contiguous_strided|SIZE=\"$SIZE\"|contiguous_strided"
-    "./target/release/contiguous_parallel_strided|SIZE=\"$SIZE\"|contiguous_parallel_strided"
-    "./target/release/contiguous_tiled|SIZE=\"$SIZE\" BLOCK_SIZE=\"$BLOCK_SIZE\"|contiguous_tiled"
-    "./target/release/contiguous_parallel_tiled|SIZE=\"$SIZE\" BLOCK_SIZE=\"$BLOCK_SIZE\"|contiguous_parallel_tiled"
+    "./target/release/naive_fragmented|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\"|naive_fragmented"
+    "./target/release/contiguous_strided|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\"|contiguous_strided"
+    "./target/release/contiguous_strided|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\" KERNEL=\"packed\"|contiguous_strided_packed"
+    "./target/release/contiguous_parallel_strided|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\"|contiguous_parallel_strided"
+    "./target/release/contiguous_tiled|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\" BLOCK_SIZE=\"$BLOCK_SIZE\"|contiguous_tiled"
+    "./target/release/contiguous_tiled|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\" KERNEL=\"packed\"|contiguous_tiled_packed"
+    "./target/release/contiguous_tiled|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\" KERNEL=\"strassen\" CUTOFF=\"$CUTOFF\"|contiguous_tiled_strassen"
+    "./target/release/contiguous_tiled|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\" KERNEL=\"recursive\"|contiguous_tiled_recursive"
+    "./target/release/contiguous_parallel_tiled|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\" BLOCK_SIZE=\"$BLOCK_SIZE\"|contiguous_parallel_tiled"
+    "./target/release/contiguous_parallel_tiled|SIZE=\"$SIZE\" DTYPE=\"$DTYPE\" KERNEL=\"strassen\" CUTOFF=\"$CUTOFF\"|contiguous_parallel_tiled_strassen"
 )
 
 detect_cores() {